@@ -0,0 +1,23 @@
+#![no_main]
+
+use advent_of_rust::solution::{days, Day};
+use libfuzzer_sys::fuzz_target;
+
+// The first byte picks the day, so one target covers the parser of every registered day
+fuzz_target!(|data: &[u8]| {
+    let Some((&pick, input)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+
+    let days = days().collect::<Vec<Day>>();
+    let day = &days[usize::from(pick) % days.len()];
+
+    // Errors are fine, only panics count as findings. Days without a parse hook parse as part
+    // of solving, so those get solved instead.
+    if day.solution.parse(input).is_none() {
+        let _ = day.solve(1, input);
+    }
+});
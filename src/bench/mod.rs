@@ -0,0 +1,322 @@
+use std::{
+    fmt::{self, Write},
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{error::SolveError, solution::Day};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(min: u64, median: u64, max: u64) -> Timings {
+        Timings {
+            min: Duration::from_nanos(min),
+            median: Duration::from_nanos(median),
+            max: Duration::from_nanos(max),
+        }
+    }
+
+    #[test]
+    fn it_summarizes_samples() {
+        let samples = [5, 1, 3].map(Duration::from_nanos).to_vec();
+        assert_eq!(Timings::from_samples(samples), Some(timings(1, 3, 5)));
+
+        let samples = [4, 1, 2, 8].map(Duration::from_nanos).to_vec();
+        assert_eq!(Timings::from_samples(samples), Some(timings(1, 3, 8)));
+
+        assert_eq!(Timings::from_samples(vec![]), None);
+    }
+
+    #[cfg(feature = "y2022")]
+    #[test]
+    fn it_benchmarks_every_step() {
+        use crate::solution::find;
+
+        let day = find(2022, 1).unwrap();
+        let steps = bench_day(&day, "1\n2\n\n4", 3)
+            .unwrap()
+            .iter()
+            .map(|measurement| measurement.step)
+            .collect::<Vec<_>>();
+        assert_eq!(steps, [Step::Parse, Step::Part1, Step::Part2]);
+    }
+
+    #[test]
+    fn it_skips_missing_steps() {
+        use crate::solution::{Answer, Solution};
+
+        // Only has the one required hook
+        struct PartOne;
+
+        impl Solution for PartOne {
+            fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+                Ok(Answer::from(input.len()))
+            }
+        }
+
+        let day = Day {
+            year: 2021,
+            day: 1,
+            solution: &PartOne,
+        };
+        let steps = bench_day(&day, "1\n2\n3", 3)
+            .unwrap()
+            .iter()
+            .map(|measurement| measurement.step)
+            .collect::<Vec<_>>();
+        assert_eq!(steps, [Step::Part1]);
+    }
+
+    #[test]
+    fn it_takes_parsing_off_the_parts() {
+        use std::thread;
+
+        use crate::solution::{Answer, Solution};
+
+        // Parsing is all the work there is
+        struct SlowParse;
+
+        impl Solution for SlowParse {
+            fn parse(&self, _input: &str) -> Option<Result<(), SolveError>> {
+                thread::sleep(Duration::from_millis(20));
+                Some(Ok(()))
+            }
+
+            fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+                self.parse(input).unwrap()?;
+                Ok(Answer::from(0))
+            }
+        }
+
+        let day = Day {
+            year: 2021,
+            day: 1,
+            solution: &SlowParse,
+        };
+        let measurements = bench_day(&day, "", 3).unwrap();
+        assert_eq!(measurements[1].step, Step::Part1);
+        assert!(measurements[0].timings.median >= Duration::from_millis(20));
+        assert!(measurements[1].timings.median < Duration::from_millis(10));
+    }
+
+    #[cfg(feature = "y2022")]
+    #[test]
+    fn it_reports_failures() {
+        use crate::solution::find;
+
+        let day = find(2022, 1).unwrap();
+        assert!(matches!(
+            bench_day(&day, "1\nx", 3),
+            Err(SolveError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn it_renders_tables() {
+        let measurements = [
+            Measurement {
+                year: 2022,
+                day: 1,
+                step: Step::Parse,
+                timings: timings(1_500, 2_000, 3_000),
+            },
+            Measurement {
+                year: 2022,
+                day: 1,
+                step: Step::Part1,
+                timings: timings(1_000_000, 2_000_000, 3_000_000),
+            },
+        ];
+
+        assert_eq!(
+            render(&measurements, Format::Markdown),
+            "| Year | Day | Step | Min | Median | Max |\n\
+             | ---: | --: | :--- | --: | -----: | --: |\n\
+             | 2022 | 1 | parse | 1.50µs | 2.00µs | 3.00µs |\n\
+             | 2022 | 1 | part1 | 1.00ms | 2.00ms | 3.00ms |\n"
+        );
+        assert_eq!(
+            render(&measurements, Format::Csv),
+            "year,day,step,min_ns,median_ns,max_ns\n\
+             2022,1,parse,1500,2000,3000\n\
+             2022,1,part1,1000000,2000000,3000000\n"
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part1 => write!(f, "part1"),
+            Step::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Some(Timings {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub step: Step,
+    pub timings: Timings,
+}
+
+// `overhead` is taken off every sample
+fn measure<T>(
+    iterations: usize,
+    overhead: Duration,
+    mut step: impl FnMut() -> Option<Result<T, SolveError>>,
+) -> Option<Result<Timings, SolveError>> {
+    let mut samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let result = step()?;
+        samples.push(start.elapsed().saturating_sub(overhead));
+
+        if let Err(err) = black_box(result) {
+            return Some(Err(err));
+        }
+    }
+
+    Timings::from_samples(samples).map(Ok)
+}
+
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    iterations: usize,
+) -> Result<Vec<Measurement>, SolveError> {
+    let mut measurements = vec![];
+    // Solutions parse their input again for each part, so the median parse time is taken off
+    let mut parse = Duration::ZERO;
+
+    for step in [Step::Parse, Step::Part1, Step::Part2] {
+        let timings = match step {
+            Step::Parse => measure(iterations, Duration::ZERO, || day.solution.parse(input)),
+            Step::Part1 => measure(iterations, parse, || day.solve(1, input)),
+            Step::Part2 => measure(iterations, parse, || day.solve(2, input)),
+        };
+
+        if let Some(timings) = timings.transpose()? {
+            if step == Step::Parse {
+                parse = timings.median;
+            }
+            measurements.push(Measurement {
+                year: day.year,
+                day: day.day,
+                step,
+                timings,
+            });
+        }
+    }
+
+    Ok(measurements)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format {s:?}, expected markdown or csv")),
+        }
+    }
+}
+
+pub fn render(measurements: &[Measurement], format: Format) -> String {
+    let mut table = String::new();
+
+    match format {
+        Format::Markdown => {
+            table.push_str("| Year | Day | Step | Min | Median | Max |\n");
+            table.push_str("| ---: | --: | :--- | --: | -----: | --: |\n");
+
+            for Measurement {
+                year,
+                day,
+                step,
+                timings,
+            } in measurements
+            {
+                writeln!(
+                    table,
+                    "| {year} | {day} | {step} | {:.2?} | {:.2?} | {:.2?} |",
+                    timings.min, timings.median, timings.max
+                )
+                .unwrap();
+            }
+        }
+        Format::Csv => {
+            table.push_str("year,day,step,min_ns,median_ns,max_ns\n");
+
+            for Measurement {
+                year,
+                day,
+                step,
+                timings,
+            } in measurements
+            {
+                writeln!(
+                    table,
+                    "{year},{day},{step},{},{},{}",
+                    timings.min.as_nanos(),
+                    timings.median.as_nanos(),
+                    timings.max.as_nanos()
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    table
+}
@@ -0,0 +1,717 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use advent_of_rust::{
+    bench::{self, Format},
+    inputs::InputStore,
+    ledger::{self, Ledger, Status},
+    runner, scaffold,
+    solution::{self, Answer},
+    submit::{Outcome, Submitter},
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a puzzle, reading its input from a file, stdin or the input store
+    Run {
+        year: u16,
+        day: u8,
+        /// Only solve the given part (1 or 2)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file, defaults to stdin when piped and the input store otherwise
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Print a JSON line with the answer and timings for each part
+        #[arg(long)]
+        json: bool,
+        /// Write the events the solver emits to this file, as JSON lines
+        #[cfg(feature = "trace")]
+        #[arg(long, value_name = "FILE", conflicts_with = "json")]
+        trace: Option<PathBuf>,
+        /// Animate the simulation in the terminal, or write it to a .gif file or a directory of PNGs
+        #[cfg(feature = "visualize")]
+        #[arg(long, value_name = "PATH", num_args = 0..=1, requires = "part", conflicts_with = "json")]
+        #[cfg_attr(feature = "trace", arg(conflicts_with = "trace"))]
+        visualize: Option<Option<PathBuf>>,
+        /// Only show every Nth frame of the visualisation
+        #[cfg(feature = "visualize")]
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "visualize")]
+        every: usize,
+        /// Frames per second of the visualisation
+        #[cfg(feature = "visualize")]
+        #[arg(long, default_value_t = 20, requires = "visualize")]
+        fps: u16,
+    },
+    /// Solve every registered day in parallel and compare with the recorded answers
+    All {
+        /// Only run the given year
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Directory holding the YYYY.toml answer ledgers
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/answers"))]
+        answers: PathBuf,
+        /// Print a JSON line per solved part instead of the summary table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a puzzle's parsed input as a Graphviz graph, for days shaped like one
+    Dot {
+        year: u16,
+        day: u8,
+        /// Puzzle input file, defaults to stdin when piped and the input store otherwise
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Download a puzzle input into the input store
+    Fetch { year: u16, day: u8 },
+    /// Submit an answer, solving the puzzle input when none is given
+    Submit {
+        year: u16,
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Time parsing and both parts of the registered solutions
+    Bench {
+        /// Only benchmark the given year
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Only benchmark the given day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Number of timed runs per step
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        /// Table format, markdown or csv
+        #[arg(short, long, default_value = "markdown")]
+        format: Format,
+    },
+    /// Check every solution against the recorded answers
+    Verify {
+        /// Only verify the given year
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Only verify the given day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Directory holding the YYYY.toml answer ledgers
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/answers"))]
+        answers: PathBuf,
+        /// Directory holding the year_YYYY/day_N example files
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/src"))]
+        examples: PathBuf,
+    },
+    /// Create and register the module for a new day
+    New {
+        year: u16,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Crate root to generate the files in
+        #[arg(long, default_value = env!("CARGO_MANIFEST_DIR"))]
+        root: PathBuf,
+    },
+    /// List the registered solutions
+    List {
+        /// Only list the given year
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+}
+
+fn read_input(year: u16, day: u8, path: Option<PathBuf>) -> Result<String, Box<dyn Error>> {
+    match path {
+        Some(path) => Ok(fs::read_to_string(path)?),
+        None => {
+            let mut input = String::new();
+            if !io::stdin().is_terminal() {
+                io::stdin().read_to_string(&mut input)?;
+            }
+            if input.is_empty() {
+                input = InputStore::from_env().load(year, day)?;
+            }
+            Ok(input)
+        }
+    }
+}
+
+fn no_solution(year: u16, day: u8) -> String {
+    if solution::YEARS.iter().any(|&(y, _)| y == year) {
+        format!("No solution for {year} day {day}")
+    } else {
+        format!("No solutions for {year} in this build, enable the y{year} feature")
+    }
+}
+
+fn run(year: u16, day: u8, part: Option<u8>, input: Option<PathBuf>, json: bool) -> ExitCode {
+    let input = match read_input(year, day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(day) = solution::find(year, day) else {
+        eprintln!("{}", no_solution(year, day));
+        return ExitCode::FAILURE;
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut solved = false;
+
+    for part in parts {
+        if json {
+            match runner::run_part(&day, part, &input, None) {
+                Some(run) if run.status == Status::Error => {
+                    eprintln!("Part {part}: {}", run.answer);
+                    return ExitCode::FAILURE;
+                }
+                Some(run) => {
+                    print!("{}", runner::render_json(&[run]));
+                    solved = true;
+                }
+                None if solved => {}
+                None => {
+                    eprintln!("No solution for {year} day {} part {part}", day.day);
+                    return ExitCode::FAILURE;
+                }
+            }
+            continue;
+        }
+
+        match day.solve(part, &input) {
+            Some(Ok(answer)) => {
+                println!("Part {part}: {answer}");
+                solved = true;
+            }
+            Some(Err(err)) => {
+                eprintln!("Part {part}: {err}");
+                return ExitCode::FAILURE;
+            }
+            None if solved => {}
+            None => {
+                eprintln!("No solution for {year} day {} part {part}", day.day);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+// Like `run`, but with the solver's events written to `path` instead of discarded
+#[cfg(feature = "trace")]
+fn trace(year: u16, day: u8, part: Option<u8>, input: Option<PathBuf>, path: &Path) -> ExitCode {
+    use std::{
+        cell::RefCell,
+        io::{BufWriter, Write},
+        rc::Rc,
+    };
+
+    use advent_of_rust::trace;
+
+    let input = match read_input(year, day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(day) = solution::find(year, day) else {
+        eprintln!("{}", no_solution(year, day));
+        return ExitCode::FAILURE;
+    };
+
+    let out = match fs::File::create(path) {
+        Ok(file) => Rc::new(RefCell::new(BufWriter::new(file))),
+        Err(err) => {
+            eprintln!("Failed to create {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    // The first write error, reported once the solver is done
+    let failed_write = Rc::new(RefCell::new(None));
+    let events = Rc::new(RefCell::new(0));
+    // A failed part still gets its events written, since they're what explains the failure
+    let mut status = ExitCode::SUCCESS;
+    let mut solved = false;
+
+    for part in part.map_or(vec![1, 2], |part| vec![part]) {
+        let sink = {
+            let (out, failed_write, events) = (out.clone(), failed_write.clone(), events.clone());
+            move |event: trace::Event| {
+                *events.borrow_mut() += 1;
+                if let Err(err) =
+                    trace::write_json(&mut *out.borrow_mut(), year, day.day, part, &event)
+                {
+                    failed_write.borrow_mut().get_or_insert(err);
+                }
+            }
+        };
+
+        match trace::with_sink(sink, || day.solve(part, &input)) {
+            Some(Ok(answer)) => {
+                println!("Part {part}: {answer}");
+                solved = true;
+            }
+            Some(Err(err)) => {
+                eprintln!("Part {part}: {err}");
+                status = ExitCode::FAILURE;
+                break;
+            }
+            None if solved => {}
+            None => {
+                eprintln!("No solution for {year} day {} part {part}", day.day);
+                status = ExitCode::FAILURE;
+                break;
+            }
+        }
+    }
+
+    let flushed = out.borrow_mut().flush();
+    if let Some(err) = failed_write.take().or(flushed.err()) {
+        eprintln!("Failed to write {}: {err}", path.display());
+        return ExitCode::FAILURE;
+    }
+
+    eprintln!("Wrote {} events to {}", events.take(), path.display());
+    status
+}
+
+// Like `run` for a single part, but with the frames the solver draws shown or saved
+#[cfg(feature = "visualize")]
+fn visualize(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: Option<PathBuf>,
+    target: Option<&Path>,
+    every: usize,
+    fps: u16,
+) -> ExitCode {
+    use std::{cell::RefCell, io::BufWriter, rc::Rc, thread, time::Duration};
+
+    use advent_of_rust::visualize::{self, Extent, Frame, GifWriter};
+
+    type Show = Box<dyn FnMut(&Frame) -> io::Result<()>>;
+
+    let input = match read_input(year, day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(day) = solution::find(year, day) else {
+        eprintln!("{}", no_solution(year, day));
+        return ExitCode::FAILURE;
+    };
+
+    let frames = Rc::new(RefCell::new(0));
+    // The first write error, reported once the solver is done
+    let failed_write = Rc::new(RefCell::new(None::<io::Error>));
+    let solve = |mut show: Show| {
+        let (frames, failed_write) = (frames.clone(), failed_write.clone());
+        let sink = move |frame: Frame| {
+            *frames.borrow_mut() += 1;
+            if let Err(err) = show(&frame) {
+                failed_write.borrow_mut().get_or_insert(err);
+            }
+        };
+        visualize::with_sink(every, sink, || day.solve(part, &input))
+    };
+
+    let result = match target {
+        None => {
+            let delay = Duration::from_secs(1) / u32::from(fps.max(1));
+            solve(Box::new(move |frame| {
+                visualize::write_ansi(&mut io::stdout(), frame)?;
+                thread::sleep(delay);
+                Ok(())
+            }))
+        }
+        // A GIF's size is fixed up front, so the solver runs once to find it
+        Some(path) if path.extension().is_some_and(|ext| ext == "gif") => {
+            let extent = Rc::new(RefCell::new(None::<Extent>));
+            let measure = extent.clone();
+            solve(Box::new(move |frame| {
+                let mut extent = measure.borrow_mut();
+                *extent = Some(extent.map_or_else(|| frame.into(), |extent| extent.union(frame)));
+                Ok(())
+            }));
+            frames.replace(0);
+
+            let writer = extent.take().map(|extent| {
+                let file = fs::File::create(path)?;
+                GifWriter::new(BufWriter::new(file), extent, fps)
+            });
+            match writer {
+                Some(Ok(mut writer)) => solve(Box::new(move |frame| writer.write(frame))),
+                Some(Err(err)) => {
+                    eprintln!("Failed to create {}: {err}", path.display());
+                    return ExitCode::FAILURE;
+                }
+                None => day.solve(part, &input),
+            }
+        }
+        Some(dir) => {
+            if let Err(err) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create {}: {err}", dir.display());
+                return ExitCode::FAILURE;
+            }
+            let (dir, mut n) = (dir.to_path_buf(), 0);
+            solve(Box::new(move |frame| {
+                let file = fs::File::create(dir.join(format!("frame_{n:05}.png")))?;
+                n += 1;
+                visualize::write_png(BufWriter::new(file), frame)
+            }))
+        }
+    };
+
+    match result {
+        Some(Ok(answer)) => println!("Part {part}: {answer}"),
+        Some(Err(err)) => {
+            eprintln!("Part {part}: {err}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("No solution for {year} day {} part {part}", day.day);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(err) = failed_write.take() {
+        eprintln!("Failed to write the frames: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    match (frames.take(), target) {
+        (0, _) => {
+            eprintln!("{year} day {} part {part} doesn't draw any frames", day.day);
+            return ExitCode::FAILURE;
+        }
+        (n, Some(path)) => eprintln!("Wrote {n} frames to {}", path.display()),
+        (_, None) => {}
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn all(year: Option<u16>, answers: &Path, json: bool) -> ExitCode {
+    let mut ledgers = vec![];
+
+    for &(y, _) in solution::YEARS
+        .iter()
+        .filter(|(y, _)| year.is_none_or(|year| *y == year))
+    {
+        match Ledger::load(answers, y) {
+            Ok(ledger) => ledgers.push((y, ledger)),
+            Err(err) => {
+                eprintln!("Failed to load answers: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let days = solution::days()
+        .filter(|d| year.is_none_or(|year| d.year == year))
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let runs = runner::run_all(&days, &InputStore::from_env(), &ledgers);
+    let elapsed = start.elapsed();
+
+    if json {
+        print!("{}", runner::render_json(&runs));
+    } else {
+        print!("{}", runner::render(&runs));
+    }
+
+    let failures = runs
+        .iter()
+        .filter(|run| matches!(run.status, Status::Fail | Status::Error))
+        .count();
+    if !json {
+        println!(
+            "\n{} parts in {elapsed:.2?}, {failures} failed",
+            runs.iter().filter(|run| run.time.is_some()).count()
+        );
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn dot(year: u16, day: u8, input: Option<PathBuf>) -> ExitCode {
+    let input = match read_input(year, day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(d) = solution::find(year, day) else {
+        eprintln!("{}", no_solution(year, day));
+        return ExitCode::FAILURE;
+    };
+
+    match d.solution.dot(&input) {
+        Some(Ok(dot)) => {
+            print!("{dot}");
+            ExitCode::SUCCESS
+        }
+        Some(Err(err)) => {
+            eprintln!("Failed to parse the input: {err}");
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("{year} day {day} has no graph to export");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(year: u16, day: u8) -> ExitCode {
+    let store = InputStore::from_env();
+
+    match store.load(year, day) {
+        Ok(_) => {
+            println!("{}", store.path(year, day).display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Failed to fetch {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(year: u16, day: u8, part: u8, answer: Option<String>) -> ExitCode {
+    let answer = match answer {
+        Some(answer) => match answer.parse() {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Str(answer),
+        },
+        None => {
+            let Some(d) = solution::find(year, day) else {
+                eprintln!("{}", no_solution(year, day));
+                return ExitCode::FAILURE;
+            };
+            let input = match InputStore::from_env().load(year, day) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("Failed to read input: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match d.solve(part, &input) {
+                Some(Ok(answer)) => answer,
+                Some(Err(err)) => {
+                    eprintln!("Part {part}: {err}");
+                    return ExitCode::FAILURE;
+                }
+                None => {
+                    eprintln!("No solution for {year} day {day} part {part}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+
+    match Submitter::from_env().submit(year, day, part, &answer) {
+        Ok(Outcome::Correct) => {
+            println!("{answer}: {}", Outcome::Correct);
+            ExitCode::SUCCESS
+        }
+        Ok(outcome) => {
+            println!("{answer}: {outcome}");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("Not submitting {answer}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn bench(year: Option<u16>, day: Option<u8>, iterations: usize, format: Format) -> ExitCode {
+    let store = InputStore::from_env();
+    let mut measurements = vec![];
+    let mut failed = false;
+
+    for d in solution::days()
+        .filter(|d| year.is_none_or(|year| d.year == year))
+        .filter(|d| day.is_none_or(|day| d.day == day))
+    {
+        let input = match store.load(d.year, d.day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Skipping {} day {}: {err}", d.year, d.day);
+                continue;
+            }
+        };
+
+        match bench::bench_day(&d, &input, iterations) {
+            Ok(results) => measurements.extend(results),
+            Err(err) => {
+                eprintln!("{} day {}: {err}", d.year, d.day);
+                failed = true;
+            }
+        }
+    }
+
+    print!("{}", bench::render(&measurements, format));
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify(year: Option<u16>, day: Option<u8>, answers: &Path, examples: &Path) -> ExitCode {
+    let store = InputStore::from_env();
+    let mut checks = vec![];
+
+    for &(y, _) in solution::YEARS
+        .iter()
+        .filter(|(y, _)| year.is_none_or(|year| *y == year))
+    {
+        let ledger = match Ledger::load(answers, y) {
+            Ok(ledger) => ledger,
+            Err(err) => {
+                eprintln!("Failed to load answers: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        for d in solution::days()
+            .filter(|d| d.year == y)
+            .filter(|d| day.is_none_or(|day| d.day == day))
+        {
+            checks.extend(ledger::verify_day(&d, examples, &store, &ledger));
+        }
+    }
+
+    print!("{}", ledger::render(&checks));
+
+    if checks
+        .iter()
+        .any(|check| matches!(check.status, Status::Fail | Status::Error))
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn new(year: u16, day: u8, root: &Path) -> ExitCode {
+    match scaffold::scaffold(root, year, day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Failed to create {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list(year: Option<u16>) -> ExitCode {
+    for day in solution::days().filter(|day| year.is_none_or(|year| day.year == year)) {
+        println!("{} day {}", day.year, day.day);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        #[cfg(feature = "trace")]
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            trace: Some(path),
+            ..
+        } => trace(year, day, part, input, &path),
+        #[cfg(feature = "visualize")]
+        Command::Run {
+            year,
+            day,
+            part: Some(part),
+            input,
+            visualize: Some(target),
+            every,
+            fps,
+            ..
+        } => visualize(year, day, part, input, target.as_deref(), every, fps),
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            json,
+            ..
+        } => run(year, day, part, input, json),
+        Command::All {
+            year,
+            answers,
+            json,
+        } => all(year, &answers, json),
+        Command::Dot { year, day, input } => dot(year, day, input),
+        Command::Fetch { year, day } => fetch(year, day),
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+        } => submit(year, day, part, answer),
+        Command::Bench {
+            year,
+            day,
+            iterations,
+            format,
+        } => bench(year, day, iterations, format),
+        Command::Verify {
+            year,
+            day,
+            answers,
+            examples,
+        } => verify(year, day, &answers, &examples),
+        Command::New { year, day, root } => new(year, day, &root),
+        Command::List { year } => list(year),
+    }
+}
@@ -0,0 +1,218 @@
+use std::{env, fmt};
+
+use sha2::{Digest, Sha256};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sends_the_session_cookie() {
+        let (url, server) = serve("200 OK", "hello");
+        let client = Client::default().with_base_url(url).with_session("abc");
+
+        assert_eq!(client.get("/2022/day/1/input").unwrap(), "hello");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(request.contains("\nCookie: session=abc"));
+    }
+
+    #[test]
+    fn it_posts_forms() {
+        let (url, server) = serve("200 OK", "ok");
+        let client = Client::default().with_base_url(url).with_session("abc");
+
+        assert_eq!(
+            client.post("/2022/day/1/answer", &[("level", "1"), ("answer", "a b")]),
+            Ok("ok".to_string())
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("\n\nlevel=1&answer=a+b"));
+    }
+
+    #[test]
+    fn it_reports_http_errors() {
+        let (url, server) = serve("404 Not Found", "not yet");
+        let client = Client::default().with_base_url(url).with_session("abc");
+
+        assert_eq!(
+            client.get("/2022/day/26/input"),
+            Err(HttpError::Status(404, "not yet".to_string()))
+        );
+        server.join().unwrap();
+
+        assert_eq!(Client::default().get("/"), Err(HttpError::NoSession));
+    }
+
+    #[test]
+    fn it_hashes_session_keys() {
+        let alice = Client::default().with_session("alice");
+        let bob = Client::default().with_session("bob");
+
+        assert_ne!(alice.session_key(), bob.session_key());
+        assert_eq!(alice.session_key().len(), 16);
+        assert_eq!(Client::default().session_key(), "default");
+    }
+}
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/amitdahan/advent-of-rust";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    NoSession,
+    Status(u16, String),
+    Network(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::NoSession => write!(f, "AOC_SESSION is not set"),
+            HttpError::Status(status, body) => write!(f, "server answered {status}: {body}"),
+            HttpError::Network(err) => write!(f, "request failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<ureq::Error> for HttpError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => {
+                HttpError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(err) => HttpError::Network(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+}
+
+impl Client {
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn from_env() -> Self {
+        let mut client = Client::default();
+
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            client = client.with_base_url(base_url);
+        }
+        if let Ok(session) = env::var("AOC_SESSION") {
+            client = client.with_session(session.trim());
+        }
+
+        client
+    }
+
+    // Hashed so the session cookie itself never ends up in a path
+    pub fn session_key(&self) -> String {
+        match &self.session {
+            Some(session) => format!("{:x}", Sha256::digest(session))[..16].to_string(),
+            None => "default".to_string(),
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, HttpError> {
+        let session = self.session.as_ref().ok_or(HttpError::NoSession)?;
+        let url = format!("{}{path}", self.base_url.trim_end_matches('/'));
+
+        Ok(ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .build()
+            .request(method, &url)
+            .set("Cookie", &format!("session={session}")))
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, HttpError> {
+        self.request("GET", path)?
+            .call()?
+            .into_string()
+            .map_err(|err| HttpError::Network(err.to_string()))
+    }
+
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, HttpError> {
+        self.request("POST", path)?
+            .send_form(form)?
+            .into_string()
+            .map_err(|err| HttpError::Network(err.to_string()))
+    }
+}
+
+// Answers a single request with the given status and body, and hands back the raw request
+#[cfg(test)]
+pub(crate) fn serve(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut request = vec![];
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_string();
+            if line.is_empty() {
+                break;
+            }
+            request.push(line);
+        }
+
+        let length = request
+            .iter()
+            .find_map(|line| {
+                line.to_lowercase()
+                    .strip_prefix("content-length: ")?
+                    .parse()
+                    .ok()
+            })
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        stream.write_all(response.as_bytes()).unwrap();
+        format!(
+            "{}\n\n{}",
+            request.join("\n"),
+            String::from_utf8(body).unwrap()
+        )
+    });
+
+    (url, server)
+}
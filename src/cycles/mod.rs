@@ -0,0 +1,167 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::Integer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 2, 3, 4, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 4 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn it_detects_cycles() {
+        assert_eq!(
+            detect_cycle(0, step),
+            Cycle {
+                start: 2,
+                length: 3
+            }
+        );
+        assert_eq!(
+            detect_cycle(7, |n| *n),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn it_fast_forwards() {
+        assert_eq!(state_after(0, 0, step), 0);
+        assert_eq!(state_after(0, 3, step), 3);
+        assert_eq!(state_after(0, 5, step), 2);
+        assert_eq!(state_after(0, 1_000_000_000, step), 4);
+    }
+
+    #[test]
+    fn it_combines_periodic_signals() {
+        let combined = combine(&[
+            Periodic::new(0, 3),
+            Periodic::new(0, 4),
+            Periodic::new(0, 6),
+        ]);
+        assert_eq!(combined, Some(Periodic::new(0, 12)));
+        assert_eq!(combined.unwrap().first_after(1), 12);
+
+        // x = 2 (mod 3), x = 3 (mod 5), x = 2 (mod 7)
+        let combined = combine(&[
+            Periodic::new(2, 3),
+            Periodic::new(3, 5),
+            Periodic::new(2, 7),
+        ]);
+        assert_eq!(combined, Some(Periodic::new(23, 105)));
+        assert_eq!(combined.unwrap().first_after(24), 128);
+
+        assert_eq!(combine(&[Periodic::new(0, 4), Periodic::new(1, 6)]), None);
+        assert_eq!(combine(&[]), None);
+    }
+}
+
+// The sequence of states visits `start` distinct states, then repeats every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+// Hash-based, so every state before the first repeat is kept around
+fn run_until_repeat<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> (Vec<S>, Option<Cycle>) {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut history = vec![initial];
+
+    while history.len() <= limit {
+        let next = step(history.last().unwrap());
+
+        if let Some(&start) = seen.get(&next) {
+            let length = history.len() - start;
+            return (history, Some(Cycle { start, length }));
+        }
+
+        seen.insert(next.clone(), history.len());
+        history.push(next);
+    }
+
+    (history, None)
+}
+
+pub fn detect_cycle<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> Cycle {
+    run_until_repeat(initial, step, usize::MAX).1.unwrap()
+}
+
+// The state after `n` applications of `step`, skipping ahead once the states start repeating
+pub fn state_after<S: Clone + Eq + Hash>(initial: S, n: usize, step: impl FnMut(&S) -> S) -> S {
+    let (mut history, cycle) = run_until_repeat(initial, step, n);
+
+    let index = match cycle {
+        Some(Cycle { start, length }) if n >= start => start + (n - start) % length,
+        _ => n,
+    };
+    history.swap_remove(index)
+}
+
+// A signal firing at every time `t` where `t % period == phase`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Periodic {
+    pub phase: u64,
+    pub period: u64,
+}
+
+impl Periodic {
+    pub fn new(phase: u64, period: u64) -> Self {
+        Periodic {
+            phase: phase % period,
+            period,
+        }
+    }
+
+    // The earliest time at or after `t` the signal fires
+    pub fn first_after(&self, t: u64) -> u64 {
+        let behind = (self.phase + self.period - t % self.period) % self.period;
+        t + behind
+    }
+
+    // Chinese remainder theorem, allowing periods that aren't coprime
+    pub fn combine(&self, other: &Periodic) -> Option<Periodic> {
+        let (a1, n1) = (i128::from(self.phase), i128::from(self.period));
+        let (a2, n2) = (i128::from(other.phase), i128::from(other.period));
+
+        let gcd = n1.extended_gcd(&n2);
+        if (a2 - a1) % gcd.gcd != 0 {
+            return None;
+        }
+
+        let modulus = n2 / gcd.gcd;
+        let period = n1.checked_mul(modulus)?;
+
+        // Both factors are below `modulus`, so their product only fits unsigned
+        let k = ((a2 - a1) / gcd.gcd).rem_euclid(modulus) as u128
+            * gcd.x.rem_euclid(modulus) as u128
+            % modulus as u128;
+        let k = k as i128;
+        let phase = (a1 + k * n1).rem_euclid(period);
+
+        Some(Periodic::new(
+            u64::try_from(phase).ok()?,
+            u64::try_from(period).ok()?,
+        ))
+    }
+}
+
+// The times at which all `signals` fire together, or `None` if they never do
+pub fn combine(signals: &[Periodic]) -> Option<Periodic> {
+    let (first, rest) = signals.split_first()?;
+    rest.iter()
+        .try_fold(*first, |combined, signal| combined.combine(signal))
+}
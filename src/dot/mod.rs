@@ -0,0 +1,123 @@
+use std::fmt::{self, Write};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_digraphs() {
+        let mut dot = Dot::digraph("network");
+        dot.node("broadcaster", &[("shape", "doublecircle")])
+            .node("a", &[])
+            .edge("broadcaster", "a", &[("label", "low")]);
+
+        assert_eq!(
+            dot.to_string(),
+            "digraph network {\n\
+             \x20   \"broadcaster\" [shape=\"doublecircle\"];\n\
+             \x20   \"a\";\n\
+             \x20   \"broadcaster\" -> \"a\" [label=\"low\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_undirected_graphs() {
+        let mut dot = Dot::graph("tunnels");
+        dot.edge("AA", "BB", &[]);
+
+        assert_eq!(
+            dot.to_string(),
+            "graph tunnels {\n    \"AA\" -- \"BB\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn it_escapes_ids() {
+        let mut dot = Dot::digraph("g");
+        dot.node("say \"hi\"\\", &[("label", "a\nb")]);
+
+        assert_eq!(
+            dot.to_string(),
+            "digraph g {\n    \"say \\\"hi\\\"\\\\\" [label=\"a\\nb\"];\n}\n"
+        );
+    }
+}
+
+// A Graphviz graph, written out in the order its nodes and edges were added
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    statements: Vec<String>,
+}
+
+fn quote(id: &str) -> String {
+    let escaped = id
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+
+    let attrs = attrs
+        .iter()
+        .map(|(key, value)| format!("{key}={}", quote(value)))
+        .collect::<Vec<_>>();
+    format!(" [{}]", attrs.join(", "))
+}
+
+impl Dot {
+    pub fn digraph(name: &str) -> Self {
+        Dot {
+            name: name.to_string(),
+            directed: true,
+            statements: vec![],
+        }
+    }
+
+    pub fn graph(name: &str) -> Self {
+        Dot {
+            name: name.to_string(),
+            directed: false,
+            statements: vec![],
+        }
+    }
+
+    pub fn node(&mut self, id: &str, attrs: &[(&str, &str)]) -> &mut Self {
+        self.statements
+            .push(format!("{}{}", quote(id), attributes(attrs)));
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[(&str, &str)]) -> &mut Self {
+        let arrow = if self.directed { "->" } else { "--" };
+        self.statements.push(format!(
+            "{} {arrow} {}{}",
+            quote(from),
+            quote(to),
+            attributes(attrs)
+        ));
+        self
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.directed { "digraph" } else { "graph" };
+
+        writeln!(f, "{kind} {} {{", self.name)?;
+        for statement in &self.statements {
+            f.write_str("    ")?;
+            f.write_str(statement)?;
+            f.write_char(';')?;
+            f.write_char('\n')?;
+        }
+        writeln!(f, "}}")
+    }
+}
@@ -0,0 +1,173 @@
+use std::{fmt, str::FromStr};
+
+use nom::{
+    error::{Error, ErrorKind},
+    Err, IResult,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{newline, u32},
+        multi::separated_list1,
+        sequence::preceded,
+    };
+
+    fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+        separated_list1(newline, preceded(tag("n="), u32))(input)
+    }
+
+    #[test]
+    fn it_locates_substrings() {
+        let input = "abc\ndef\nghi";
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(
+            SolveError::parse(input, &line[1..], "bad"),
+            SolveError::Parse {
+                line: 2,
+                column: 2,
+                message: "bad".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn it_locates_non_ascii_text() {
+        let input = "é\nαβγ";
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(
+            SolveError::parse(input, &line[4..], "bad").to_string(),
+            "line 2, column 3: bad"
+        );
+        assert_eq!(locate(input, 6), (2, 2));
+        assert_eq!(locate(input, 100), (2, 4));
+    }
+
+    #[test]
+    fn it_parses_numbers() {
+        let input = "12\n-3\nx4";
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(parse_number::<i32>(input, lines[1]), Ok(-3));
+        assert_eq!(
+            parse_number::<i32>(input, lines[2])
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 1: expected a number, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn it_parses_complete_input() {
+        assert_eq!(parse_all("n=1\nn=2\n", numbers), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn it_reports_parser_errors() {
+        let error = parse_all("x=1", numbers).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: unexpected token");
+    }
+
+    #[test]
+    fn it_reports_trailing_input() {
+        let error = parse_all("n=1\nn=2\nn=x\n", numbers).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: unexpected input");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    NoSolution(String),
+}
+
+impl SolveError {
+    // `at` must be a slice of `input`, pointing at the offending text.
+    pub fn parse(input: &str, at: &str, message: impl Into<String>) -> SolveError {
+        let offset = (at.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        let (line, column) = locate(input, offset);
+
+        SolveError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> SolveError {
+        SolveError::NoSolution(message.into())
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {line}, column {column}: {message}"),
+            SolveError::NoSolution(message) => write!(f, "no solution: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+// Columns count characters, and an offset inside one points at that character
+fn locate(input: &str, offset: usize) -> (usize, usize) {
+    input
+        .char_indices()
+        .take_while(|&(i, c)| i + c.len_utf8() <= offset)
+        .fold((1, 1), |(line, column), (_, c)| match c {
+            '\n' => (line + 1, 1),
+            _ => (line, column + 1),
+        })
+}
+
+fn describe(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Tag => "unexpected token",
+        ErrorKind::Char => "unexpected character",
+        ErrorKind::Digit => "expected a number",
+        ErrorKind::Alpha => "expected a letter",
+        ErrorKind::Eof => "unexpected end of input",
+        ErrorKind::CrLf => "expected a line break",
+        kind => kind.description(),
+    }
+}
+
+// `s` must be a slice of `input`, so that failures can be located.
+pub fn parse_number<T: FromStr>(input: &str, s: &str) -> Result<T, SolveError> {
+    s.parse()
+        .map_err(|_| SolveError::parse(input, s, format!("expected a number, found {s:?}")))
+}
+
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, SolveError> {
+    match parser(input) {
+        Ok((rest, output)) => {
+            let rest = rest.trim_start();
+            if rest.is_empty() {
+                Ok(output)
+            } else {
+                Err(SolveError::parse(input, rest, "unexpected input"))
+            }
+        }
+        Err(Err::Error(Error { input: at, code }) | Err::Failure(Error { input: at, code })) => {
+            Err(SolveError::parse(input, at, describe(&code)))
+        }
+        Err(Err::Incomplete(_)) => Err(SolveError::parse(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
@@ -0,0 +1,296 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use itertools::Itertools;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_measures_distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 5);
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!((b - a).signum(), Point2::new(-1, 1));
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(-1, 2, 6)), 5);
+        assert_eq!(Point3::new(1, 2, 3).chebyshev(Point3::new(-1, 2, 6)), 3);
+    }
+
+    #[test]
+    fn it_turns() {
+        use Direction::*;
+
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(Left.turn_left(), Down);
+        assert_eq!(Right.opposite(), Left);
+        assert!(Direction::ALL
+            .iter()
+            .all(|dir| dir.turn_left().turn_left() == dir.opposite()));
+
+        assert_eq!(Point2::new(2, 2) + Up, Point2::new(2, 1));
+        assert_eq!(Point2::new(2, 2) + Right * 3, Point2::new(5, 2));
+        assert_eq!(Direction::try_from('v'), Ok(Down));
+        assert_eq!(Direction::try_from('x'), Err('x'));
+    }
+
+    #[test]
+    fn it_measures_polygons() {
+        // A 4x3 rectangle, with the unit squares of its outline included
+        let vertices = [
+            Point2::new(0, 0),
+            Point2::new(3, 0),
+            Point2::new(3, 2),
+            Point2::new(0, 2),
+        ];
+
+        assert_eq!(shoelace_area(&vertices), 6);
+        assert_eq!(perimeter(&vertices), 10);
+        assert_eq!(interior_points(6, 10), 2);
+        assert_eq!(interior_points(0, 8), 0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    pub fn signum(self) -> Point2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2> {
+        Direction::ALL.into_iter().map(move |dir| self + dir)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2> {
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter(|&delta| delta != (0, 0))
+            .map(move |(dy, dx)| self + Point2::new(dx, dy))
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Add<Direction> for Point2 {
+    type Output = Point2;
+
+    fn add(self, dir: Direction) -> Point2 {
+        self + Point2::from(dir)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, dir: Direction) {
+        *self = *self + dir;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, n: i64) -> Point2 {
+        Point2::new(self.x * n, self.y * n)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+// Screen orientation: `Up` decreases y, like moving to a previous line of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+
+    // For `Grid::offset`
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+impl From<Direction> for Point2 {
+    fn from(dir: Direction) -> Self {
+        let (dx, dy) = dir.delta();
+        Point2::new(dx as i64, dy as i64)
+    }
+}
+
+impl Mul<i64> for Direction {
+    type Output = Point2;
+
+    fn mul(self, n: i64) -> Point2 {
+        Point2::from(self) * n
+    }
+}
+
+// Accepts both the `URDL` letters and the `^>v<` arrows puzzles use
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            'U' | '^' => Ok(Direction::Up),
+            'R' | '>' => Ok(Direction::Right),
+            'D' | 'v' => Ok(Direction::Down),
+            'L' | '<' => Ok(Direction::Left),
+            _ => Err(c),
+        }
+    }
+}
+
+// Area enclosed by the polygon through `vertices`, in either winding order
+pub fn shoelace_area(vertices: &[Point2]) -> u64 {
+    let doubled = vertices
+        .iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>();
+
+    doubled.unsigned_abs() / 2
+}
+
+// Length of the closed outline, for polygons whose edges are axis-aligned
+pub fn perimeter(vertices: &[Point2]) -> u64 {
+    vertices
+        .iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| a.manhattan(*b))
+        .sum()
+}
+
+// Pick's theorem: lattice points strictly inside a polygon, from its area and the lattice points on its boundary
+pub fn interior_points(area: u64, boundary: u64) -> u64 {
+    // An outline that folds back on itself has no area and nothing inside
+    (area + 1).saturating_sub(boundary / 2)
+}
@@ -0,0 +1,335 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::SolveError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, SolveError> {
+        Grid::parse(input, |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn it_parses_text() {
+        let grid = digits("123\r\n456\r\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.position(|&d| d == 5), Some((1, 1)));
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn it_reports_malformed_grids() {
+        assert_eq!(
+            digits("123\n4x6").unwrap_err().to_string(),
+            "line 2, column 2: unexpected character 'x'"
+        );
+        assert_eq!(
+            digits("123\n45").unwrap_err().to_string(),
+            "line 2, column 1: rows must have the same width"
+        );
+        assert_eq!(
+            digits("\n").unwrap_err().to_string(),
+            "line 1, column 1: expected a grid"
+        );
+    }
+
+    #[test]
+    fn it_iterates_neighbors() {
+        let grid = digits("123\n456\n789").unwrap();
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.offset((2, 0), (1, 0)), None);
+    }
+
+    #[test]
+    fn it_rotates_and_transposes() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "36\n25\n14\n");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_counterclockwise(),
+            grid.clone()
+        );
+    }
+
+    #[test]
+    fn it_wraps_around() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!(grid.get_wrapping((-1, 0)), &3);
+        assert_eq!(grid.get_wrapping((4, 3)), &5);
+        assert_eq!(grid.get_wrapping((-3, -2)), &1);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    // Trailing newlines and CRLF line endings are accepted.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, SolveError> {
+        Grid::parse_block(input, input, cell)
+    }
+
+    // Like `parse`, but errors point into `input`, of which `block` must be a slice.
+    pub fn parse_block(
+        input: &str,
+        block: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, SolveError> {
+        let lines = block
+            .trim_end_matches(['\r', '\n'])
+            .lines()
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+
+        if width == 0 {
+            return Err(SolveError::parse(input, block, "expected a grid"));
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in &lines {
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    SolveError::parse(input, &line[i..], format!("unexpected character {c:?}"))
+                })?;
+                cells.push(value);
+            }
+            if line.chars().count() != width {
+                return Err(SolveError::parse(
+                    input,
+                    line,
+                    "rows must have the same width",
+                ));
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if self.contains((x, y)) {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if self.contains((x, y)) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_signed(&self, (x, y): (i64, i64)) -> Option<&T> {
+        self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+    }
+
+    // Treats the grid as tiling the plane infinitely in every direction
+    pub fn get_wrapping(&self, (x, y): (i64, i64)) -> &T {
+        &self[(
+            x.rem_euclid(self.width as i64) as usize,
+            y.rem_euclid(self.height as i64) as usize,
+        )]
+    }
+
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // In reading order: up, left, right, down
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    // In reading order, diagonals included
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&delta| delta != (0, 0))
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> usize) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| self.cells[source(x, y)].clone())
+                .collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| x * self.width + y)
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| {
+            (self.height - 1 - x) * self.width + y
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| {
+            x * self.width + (self.width - 1 - y)
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get((x, y))
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut((x, y))
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::client::{Client, HttpError};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::serve;
+
+    fn store(name: &str, client: Client) -> InputStore {
+        let dir = env::temp_dir().join(format!("advent-of-rust-inputs-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        InputStore::new(dir, client)
+    }
+
+    #[test]
+    fn it_fetches_and_caches_inputs() {
+        let (url, server) = serve("200 OK", "1\n2\n3\n");
+        let store = store(
+            "fetch",
+            Client::default().with_base_url(url).with_session("abc"),
+        );
+
+        assert_eq!(store.cached(2022, 1), None);
+        assert_eq!(store.load(2022, 1).unwrap(), "1\n2\n3\n");
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /2022/day/1/input HTTP/1.1"));
+
+        // The server only answers once, so this has to come from the cache
+        assert_eq!(store.load(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            fs::read_to_string(store.path(2022, 1)).unwrap(),
+            "1\n2\n3\n"
+        );
+    }
+
+    #[test]
+    fn it_keys_the_cache_by_session() {
+        let store = store("sessions", Client::default());
+        let alice = InputStore::new(&store.dir, Client::default().with_session("alice"));
+        let bob = InputStore::new(&store.dir, Client::default().with_session("bob"));
+
+        assert_ne!(alice.path(2023, 5), bob.path(2023, 5));
+        assert_ne!(alice.path(2023, 5), store.path(2023, 5));
+        assert!(store.path(2023, 5).ends_with("default/year_2023/day_5.in"));
+        assert!(!alice.path(2023, 5).to_string_lossy().contains("alice"));
+    }
+
+    #[test]
+    fn it_reports_http_errors() {
+        let (url, server) = serve(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint",
+        );
+        let store = store(
+            "http-error",
+            Client::default().with_base_url(url).with_session("abc"),
+        );
+
+        assert!(matches!(
+            store.load(2022, 26),
+            Err(InputError::Fetch(HttpError::Status(404, _)))
+        ));
+        server.join().unwrap();
+        assert_eq!(store.cached(2022, 26), None);
+    }
+
+    #[test]
+    fn it_needs_a_session_to_fetch() {
+        assert!(matches!(
+            store("no-session", Client::default()).load(2022, 1),
+            Err(InputError::Fetch(HttpError::NoSession))
+        ));
+    }
+}
+
+pub const PUZZLE_INPUT: &str = "input.in";
+
+#[derive(Debug)]
+pub enum InputError {
+    Fetch(HttpError),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Fetch(err) => {
+                write!(f, "input is not cached and could not be fetched: {err}")
+            }
+            InputError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn default_dir() -> PathBuf {
+    env::var_os("AOC_INPUTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+}
+
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    dir: PathBuf,
+    client: Client,
+}
+
+impl InputStore {
+    pub fn new(dir: impl Into<PathBuf>, client: Client) -> Self {
+        InputStore {
+            dir: dir.into(),
+            client,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        InputStore::new(default_dir(), Client::from_env())
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(self.client.session_key())
+            .join(format!("year_{year}"))
+            .join(format!("day_{day}.in"))
+    }
+
+    pub fn cached(&self, year: u16, day: u8) -> Option<String> {
+        fs::read_to_string(self.path(year, day)).ok()
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let input = self
+            .client
+            .get(&format!("/{year}/day/{day}/input"))
+            .map_err(InputError::Fetch)?;
+
+        let path = self.path(year, day);
+        let write = |path: &Path| {
+            fs::create_dir_all(path.parent().unwrap_or(path))?;
+            fs::write(path, &input)
+        };
+        write(&path).map_err(|err| InputError::Io(path.clone(), err))?;
+
+        Ok(input)
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> Result<String, InputError> {
+        match self.cached(year, day) {
+            Some(input) => Ok(input),
+            None => self.fetch(year, day),
+        }
+    }
+}
+
+// For the ignored `it_solves_input` tests, which are only run when the inputs are around
+#[cfg(all(test, any(feature = "y2021", feature = "y2022", feature = "y2023")))]
+pub(crate) fn test_input(year: u16, day: u8) -> String {
+    InputStore::from_env()
+        .load(year, day)
+        .unwrap_or_else(|err| panic!("no input for {year} day {day}: {err}"))
+}
@@ -0,0 +1,343 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    inputs::{InputStore, PUZZLE_INPUT},
+    solution::{input_path, Day},
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = r#"
+        [[answer]]
+        day = 1
+        input = "example.in"
+        part1 = "24000"
+        part2 = "45000"
+
+        [[answer]]
+        day = 1
+        input = "other.in"
+        part1 = "1"
+    "#;
+
+    #[cfg(feature = "y2022")]
+    fn inputs(name: &str) -> (PathBuf, InputStore) {
+        use crate::client::Client;
+
+        let root = std::env::temp_dir().join(format!("advent-of-rust-ledger-{name}"));
+        let dir = root.join("year_2022").join("day_1");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("example.in"), "10000\n14000\n\n20000\n\n1000").unwrap();
+
+        let store = InputStore::new(root.join("store"), Client::default());
+        fs::create_dir_all(store.path(2022, 1).parent().unwrap()).unwrap();
+        fs::write(store.path(2022, 1), "1\n\n2").unwrap();
+
+        (root, store)
+    }
+
+    #[test]
+    fn it_parses_ledgers() {
+        let ledger: Ledger = LEDGER.parse().unwrap();
+        assert_eq!(ledger.expected(1, "example.in", 1), Some("24000"));
+        assert_eq!(ledger.expected(1, "example.in", 2), Some("45000"));
+        assert_eq!(ledger.expected(1, "other.in", 2), None);
+        assert_eq!(ledger.expected(2, "example.in", 1), None);
+        assert!("[[answer]]\nday = \"one\"".parse::<Ledger>().is_err());
+    }
+
+    #[test]
+    fn it_loads_missing_ledgers_as_empty() {
+        let dir = std::env::temp_dir().join("advent-of-rust-ledger-empty");
+        assert_eq!(Ledger::load(&dir, 2022).unwrap(), Ledger::default());
+    }
+
+    #[cfg(feature = "y2022")]
+    #[test]
+    fn it_verifies_days() {
+        use crate::solution::find;
+
+        let ledger: Ledger = LEDGER.replace("45000", "46000").parse().unwrap();
+        let (examples, store) = inputs("verify");
+        let checks = verify_day(&find(2022, 1).unwrap(), &examples, &store, &ledger)
+            .into_iter()
+            .map(|check| (check.input, check.part, check.status, check.actual))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            checks,
+            [
+                (
+                    "example.in".to_string(),
+                    1,
+                    Status::Pass,
+                    "24000".to_string()
+                ),
+                (
+                    "example.in".to_string(),
+                    2,
+                    Status::Fail,
+                    "45000".to_string()
+                ),
+                (
+                    "other.in".to_string(),
+                    1,
+                    Status::Missing,
+                    "no input file".to_string()
+                ),
+                ("input.in".to_string(), 1, Status::Missing, "2".to_string()),
+                ("input.in".to_string(), 2, Status::Missing, "3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_renders_reports() {
+        let checks = [
+            Check {
+                year: 2022,
+                day: 1,
+                input: "example.in".to_string(),
+                part: 1,
+                status: Status::Pass,
+                expected: Some("24000".to_string()),
+                actual: "24000".to_string(),
+            },
+            Check {
+                year: 2022,
+                day: 10,
+                input: "input.in".to_string(),
+                part: 2,
+                status: Status::Fail,
+                expected: Some("#.\n.#".to_string()),
+                actual: ".#\n#.".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            render(&checks),
+            "| Year | Day | Input | Part | Status | Expected | Actual |\n\
+             | ---: | --: | :---- | ---: | :----- | :------- | :----- |\n\
+             | 2022 | 1 | example.in | 1 | pass | 24000 | 24000 |\n\
+             | 2022 | 10 | input.in | 2 | FAIL | #.<br>.# | .#<br>#. |\n"
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Entry {
+    pub day: u8,
+    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Entry {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Ledger {
+    #[serde(default, rename = "answer")]
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            LedgerError::Toml(path, err) => write!(f, "{}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl FromStr for Ledger {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+impl Ledger {
+    pub fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    pub fn load(dir: &Path, year: u16) -> Result<Self, LedgerError> {
+        let path = Self::path(dir, year);
+
+        match fs::read_to_string(&path) {
+            Ok(text) => text.parse().map_err(|err| LedgerError::Toml(path, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(err) => Err(LedgerError::Io(path, err)),
+        }
+    }
+
+    pub fn entry(&self, day: u8, input: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.input == input)
+    }
+
+    pub fn expected(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.entry(day, input)?.part(part)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+            Status::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub part: u8,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+fn read_input(
+    day: &Day,
+    examples: &Path,
+    store: &InputStore,
+    input: &str,
+) -> Result<String, String> {
+    if input == PUZZLE_INPUT {
+        store.load(day.year, day.day).map_err(|err| err.to_string())
+    } else {
+        fs::read_to_string(input_path(examples, day.year, day.day, input))
+            .map_err(|_| "no input file".to_string())
+    }
+}
+
+fn check_input(
+    day: &Day,
+    input: &str,
+    contents: &Result<String, String>,
+    parts: &[(u8, Option<&str>)],
+) -> Vec<Check> {
+    parts
+        .iter()
+        .filter_map(|&(part, expected)| {
+            let (status, actual) = match contents {
+                Err(err) => (Status::Missing, err.clone()),
+                Ok(contents) => match (day.solve(part, contents)?, expected) {
+                    (Err(err), _) => (Status::Error, err.to_string()),
+                    (Ok(answer), None) => (Status::Missing, answer.to_string()),
+                    (Ok(answer), Some(expected)) if answer.to_string() == expected => {
+                        (Status::Pass, answer.to_string())
+                    }
+                    (Ok(answer), Some(_)) => (Status::Fail, answer.to_string()),
+                },
+            };
+
+            Some(Check {
+                year: day.year,
+                day: day.day,
+                input: input.to_string(),
+                part,
+                status,
+                expected: expected.map(str::to_string),
+                actual,
+            })
+        })
+        .collect()
+}
+
+pub fn verify_day(day: &Day, examples: &Path, store: &InputStore, ledger: &Ledger) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for entry in ledger.entries.iter().filter(|entry| entry.day == day.day) {
+        let contents = read_input(day, examples, store, &entry.input);
+        let parts = [1, 2]
+            .into_iter()
+            .filter_map(|part| Some((part, Some(entry.part(part)?))))
+            .collect::<Vec<_>>();
+        checks.extend(check_input(day, &entry.input, &contents, &parts));
+    }
+
+    if ledger.entry(day.day, PUZZLE_INPUT).is_none() {
+        if let Ok(contents) = store.load(day.year, day.day) {
+            checks.extend(check_input(
+                day,
+                PUZZLE_INPUT,
+                &Ok(contents),
+                &[(1, None), (2, None)],
+            ));
+        }
+    }
+
+    checks
+}
+
+pub(crate) fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub fn render(checks: &[Check]) -> String {
+    let mut table = String::new();
+
+    table.push_str("| Year | Day | Input | Part | Status | Expected | Actual |\n");
+    table.push_str("| ---: | --: | :---- | ---: | :----- | :------- | :----- |\n");
+
+    for check in checks {
+        writeln!(
+            table,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            check.year,
+            check.day,
+            check.input,
+            check.part,
+            check.status,
+            cell(check.expected.as_deref().unwrap_or("-")),
+            cell(&check.actual),
+        )
+        .unwrap();
+    }
+
+    table
+}
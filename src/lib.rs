@@ -1,3 +1,28 @@
+#![allow(clippy::items_after_test_module)]
+
+pub mod bench;
+pub mod client;
+pub mod cycles;
+pub mod dot;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod inputs;
+pub mod ledger;
+pub mod memo;
+pub mod ocr;
+pub mod parsers;
+pub mod ranges;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod trace;
+pub mod visualize;
+#[cfg(feature = "y2021")]
 pub mod year_2021;
+#[cfg(feature = "y2022")]
 pub mod year_2022;
+#[cfg(feature = "y2023")]
 pub mod year_2023;
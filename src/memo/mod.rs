@@ -0,0 +1,161 @@
+use std::{collections::HashMap, fmt, hash::Hash};
+
+use crate::trace_event;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn it_memoizes_recursive_calls() {
+        let mut memo = Memo::new();
+
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 87,
+                misses: 89
+            }
+        );
+
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.stats().hits, 88);
+    }
+
+    #[test]
+    fn it_reports_stats() {
+        let mut memo = Memo::new();
+        memo.get_or_insert_with("a", |_| 1);
+        memo.get_or_insert_with("a", |_| unreachable!());
+        memo.get_or_insert_with("a", |_| unreachable!());
+        memo.get_or_insert_with("b", |_| 2);
+
+        assert_eq!(memo.get(&"b"), Some(&2));
+        assert_eq!(memo.stats().hit_rate(), 0.5);
+        assert_eq!(
+            memo.stats().to_string(),
+            "2 hits, 2 misses (50.0% hit rate)"
+        );
+
+        #[cfg(feature = "trace")]
+        {
+            let ((), events) = crate::trace::record(|| memo.trace_stats("letters"));
+            assert_eq!(
+                events[0].fields,
+                [
+                    ("memo", "letters".to_string()),
+                    ("hits", "2".to_string()),
+                    ("misses", "2".to_string())
+                ]
+            );
+        }
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), MemoStats::default());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+// A cache for recursive solvers, which get the memo back to make their own recursive calls
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            stats: MemoStats::default(),
+        }
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    // Doesn't count towards the stats
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    // Reports the stats as a `memo stats` trace event, so `aoc run --trace` shows how much the
+    // cache saved
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    pub fn trace_stats(&self, name: &str) {
+        trace_event!(
+            "memo stats",
+            memo = name,
+            hits = self.stats.hits,
+            misses = self.stats.misses
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
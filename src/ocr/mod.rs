@@ -0,0 +1,183 @@
+use std::ops::Range;
+
+use crate::grid::Grid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(art: &str) -> Grid<bool> {
+        Grid::parse(art, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    // Lays out every glyph of `font` the way the puzzles do, one blank column apart
+    fn alphabet(font: Font) -> (String, Grid<bool>) {
+        let height = font[0].1.len();
+        let rows = (0..height)
+            .map(|y| {
+                font.iter()
+                    .map(|(_, glyph)| glyph[y])
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (font.iter().map(|(c, _)| c).collect(), image(&rows))
+    }
+
+    #[test]
+    fn it_decodes_small_letters() {
+        let crt = image(
+            "###..####.###...##....##.####.#....#..#.\n\
+             #..#....#.#..#.#..#....#.#....#....#.#..\n\
+             ###....#..#..#.#..#....#.###..#....##...\n\
+             #..#..#...###..####....#.#....#....#.#..\n\
+             #..#.#....#....#..#.#..#.#....#....#.#..\n\
+             ###..####.#....#..#..##..####.####.#..#.",
+        );
+
+        assert_eq!(decode(&crt), Some("BZPAJELK".to_string()));
+    }
+
+    #[test]
+    fn it_decodes_every_glyph() {
+        for font in [FONT_4X6, FONT_6X10] {
+            let (letters, image) = alphabet(font);
+            assert_eq!(decode(&image), Some(letters));
+        }
+    }
+
+    #[test]
+    fn it_rejects_unknown_glyphs() {
+        let (_, mut image) = alphabet(FONT_4X6);
+        image[(0, 0)] = true;
+
+        assert_eq!(decode(&image), None);
+        assert_eq!(decode(&Grid::filled(10, 6, false)), None);
+        assert_eq!(decode(&Grid::filled(10, 7, true)), None);
+    }
+
+    #[test]
+    fn it_renders_images() {
+        let art = ".##.\n#..#";
+        assert_eq!(render(&image(art)), art);
+    }
+}
+
+type Font = &'static [(char, &'static [&'static str])];
+
+// The letters AoC draws 4 pixels wide and 6 high, e.g. 2016 day 8, 2019 day 8 and 2022 day 10
+const FONT_4X6: Font = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// The letters AoC draws 6 pixels wide and 10 high, e.g. 2018 day 10
+#[rustfmt::skip]
+const FONT_6X10: Font = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+// Draws lit pixels as `#` and dark ones as `.`, without a trailing newline
+pub fn render(image: &Grid<bool>) -> String {
+    image
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The runs of columns with at least one lit pixel, i.e. one per letter
+fn spans(rows: &[&str]) -> Vec<Range<usize>> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.as_bytes().get(x) == Some(&b'#'));
+
+    let mut spans = vec![];
+    let mut start = None;
+    for x in 0..=width {
+        match (start, x < width && lit(x)) {
+            (None, true) => start = Some(x),
+            (Some(from), false) => {
+                spans.push(from..x);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn cut<'a>(rows: &[&'a str], span: &Range<usize>) -> Vec<&'a str> {
+    rows.iter().map(|row| &row[span.clone()]).collect()
+}
+
+fn recognize(font: Font, glyph: &[&str]) -> Option<char> {
+    font.iter().find_map(|&(letter, art)| {
+        let spans = spans(art);
+        let span = spans.first()?.start..spans.last()?.end;
+        (cut(art, &span) == glyph).then_some(letter)
+    })
+}
+
+// Reads the letters in an image drawn in either AoC font, picked by the image's height.
+// Letters are told apart by the blank columns between them, so `None` means some glyph
+// isn't in the font.
+pub fn decode(image: &Grid<bool>) -> Option<String> {
+    let font = match image.height() {
+        6 => FONT_4X6,
+        10 => FONT_6X10,
+        _ => return None,
+    };
+
+    let rendered = render(image);
+    let rows = rendered.lines().collect::<Vec<_>>();
+    let spans = spans(&rows);
+    if spans.is_empty() {
+        return None;
+    }
+
+    spans
+        .iter()
+        .map(|span| recognize(font, &cut(&rows, span)))
+        .collect()
+}
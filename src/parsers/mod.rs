@@ -0,0 +1,105 @@
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, line_ending, none_of, one_of, space0, space1},
+    combinator::{map_opt, map_res, opt, recognize, value},
+    error::Error,
+    multi::{many1, separated_list1},
+    sequence::pair,
+    IResult, Parser,
+};
+
+use crate::grid::Grid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::parse_all;
+
+    #[test]
+    fn it_parses_lines() {
+        assert_eq!(parse_all("1\n2\n3\n", lines(int::<u8>)), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_all("1\r\n2\r\n", lines(int::<u8>)), Ok(vec![1, 2]));
+        assert_eq!(
+            parse_all("1\nx\n", lines(int::<u8>))
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: unexpected input"
+        );
+    }
+
+    #[test]
+    fn it_parses_blocks() {
+        let input = "1\n2\n\n3\n";
+        assert_eq!(
+            parse_all(input, blocks(lines(int::<u8>))),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+
+        let input = "1\r\n2\r\n\r\n3\r\n\r\n";
+        assert_eq!(
+            parse_all(input, blocks(lines(int::<u8>))),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn it_parses_ints() {
+        assert_eq!(parse_all("-1,2, +3 4", ints::<i32>), Ok(vec![-1, 2, 3, 4]));
+        assert_eq!(parse_all("12", ints::<u64>), Ok(vec![12]));
+        assert!(parse_all("-1", ints::<u64>).is_err());
+    }
+
+    #[test]
+    fn it_parses_char_grids() {
+        let grid = parse_all("#.\r\n.#\r\n", char_grid(|c| Some(c == '#'))).unwrap();
+        assert_eq!(grid.row(1), [false, true]);
+
+        let grids = parse_all("#.\n.#\n\n##\n", blocks(char_grid(|c| Some(c == '#')))).unwrap();
+        assert_eq!(grids.len(), 2);
+
+        assert!(parse_all("#.\n#\n", char_grid(|c| Some(c == '#'))).is_err());
+        assert!(parse_all("#x\n", char_grid(|c| (c != 'x').then_some(c))).is_err());
+    }
+}
+
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, item)
+}
+
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    value((), pair(line_ending, line_ending))(input)
+}
+
+// Groups of lines separated by an empty line
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(blank_line, block)
+}
+
+pub fn int<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+// Separated by commas, spaces or both
+pub fn ints<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(alt((recognize(pair(char(','), space0)), space1)), int)(input)
+}
+
+// Rows must all be the same width; `cell` rejects a character by returning `None`
+pub fn char_grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input| {
+        map_opt(
+            lines(many1(map_opt(none_of("\r\n"), &cell))),
+            Grid::from_rows,
+        )(input)
+    }
+}
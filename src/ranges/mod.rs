@@ -0,0 +1,251 @@
+use std::ops::{Range, RangeInclusive};
+
+use num::PrimInt;
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> RangeSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn it_merges_ranges() {
+        let ranges = set(&[5..8, 0..2, 2..3, 7..10, 12..12]);
+
+        assert_eq!(ranges.ranges(), [0..3, 5..10]);
+        assert_eq!(ranges.len(), 8);
+        assert_eq!(ranges.min(), Some(0));
+        assert!(ranges.contains(9));
+        assert!(!ranges.contains(3));
+        assert_eq!(RangeSet::from(1..=3).ranges(), [1..4]);
+    }
+
+    #[test]
+    fn it_combines_sets() {
+        let a = set(&[0..5, 10..15]);
+        let b = set(&[3..12]);
+
+        assert_eq!(a.union(&b).ranges(), [0..15]);
+        assert_eq!(a.intersection(&b).ranges(), [3..5, 10..12]);
+        assert_eq!(a.difference(&b).ranges(), [0..3, 12..15]);
+        assert_eq!(b.difference(&a).ranges(), [5..10]);
+        assert!(a.is_superset(&set(&[1..4, 11..12])));
+        assert!(!a.is_superset(&b));
+        assert!(RangeSet::<i64>::new().is_empty());
+    }
+
+    #[test]
+    fn it_remaps_ranges() {
+        let map = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+
+        assert_eq!(map.apply(&set(&[79..93])).ranges(), [81..95]);
+        assert_eq!(
+            map.apply(&set(&[45..52, 99..101])).ranges(),
+            [45..50, 51..54, 100..101]
+        );
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(10), 10);
+    }
+
+    #[test]
+    fn it_handles_huge_ranges() {
+        let a = RangeSet::from(0..u64::MAX);
+        let b = RangeSet::from(1..u64::MAX - 1);
+
+        assert_eq!(a.difference(&b).len(), 2);
+        assert_eq!(a.intersection(&b).len(), u64::MAX - 2);
+        assert_eq!(RangeSet::from(1..=u64::MAX).ranges(), [1..u64::MAX]);
+        assert_eq!(RangeSet::from(0..=u8::MAX).len(), u8::MAX);
+    }
+}
+
+// Half-open ranges, kept sorted, disjoint and non-adjacent
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Total number of values in the set
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, range| len + (range.end - range.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges.iter().any(|range| range.contains(&value))
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Everything overlapping or touching `range` gets merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = self.ranges[first..last].iter().fold(range, |merged, r| {
+            merged.start.min(r.start)..merged.end.max(r.end)
+        });
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if start < other.ranges[k].start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        RangeSet { ranges }
+    }
+
+    pub fn is_superset(&self, other: &RangeSet<T>) -> bool {
+        other.difference(self).is_empty()
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        RangeSet::from_iter([range])
+    }
+}
+
+// A half-open range can't reach past `T::MAX`, so that value is left out
+impl<T: PrimInt> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        RangeSet::from(*range.start()..range.end().saturating_add(T::one()))
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+// Piecewise-linear remapping: each source range is shifted to start at its destination,
+// and values outside every source range map to themselves
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RangeMap<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T: PrimInt> RangeMap<T> {
+    pub fn new() -> Self {
+        RangeMap { pieces: vec![] }
+    }
+
+    // Where source ranges overlap, the first one inserted wins
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        self.pieces.push((source, destination));
+    }
+
+    pub fn get(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    pub fn apply(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut unmapped = set.clone();
+        let mut mapped = RangeSet::new();
+
+        for (source, destination) in &self.pieces {
+            let source_set = RangeSet::from(source.clone());
+
+            for range in unmapped.intersection(&source_set).ranges {
+                mapped.insert(
+                    *destination + (range.start - source.start)
+                        ..*destination + (range.end - source.start),
+                );
+            }
+            unmapped = unmapped.difference(&source_set);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: PrimInt> FromIterator<(Range<T>, T)> for RangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        RangeMap {
+            pieces: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    inputs::{InputStore, PUZZLE_INPUT},
+    ledger::{cell, Ledger, Status},
+    solution::Day,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "y2022")]
+    #[test]
+    fn it_runs_every_part() {
+        use std::{env, fs};
+
+        use crate::{client::Client, solution::find};
+
+        let store = InputStore::new(
+            env::temp_dir().join("advent-of-rust-runner"),
+            Client::default(),
+        );
+        fs::create_dir_all(store.path(2022, 1).parent().unwrap()).unwrap();
+        fs::write(store.path(2022, 1), "1\n2\n\n4").unwrap();
+        fs::write(store.path(2022, 2), "A Y\nB X").unwrap();
+        let _ = fs::remove_file(store.path(2022, 3));
+
+        let ledger: Ledger = r#"
+            [[answer]]
+            day = 1
+            input = "input.in"
+            part1 = "4"
+            part2 = "5"
+        "#
+        .parse()
+        .unwrap();
+
+        let days = [
+            find(2022, 1).unwrap(),
+            find(2022, 2).unwrap(),
+            find(2022, 3).unwrap(),
+        ];
+        let runs = run_all(&days, &store, &[(2022, ledger)])
+            .into_iter()
+            .map(|run| (run.day, run.part, run.status, run.answer))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            runs,
+            [
+                (1, Some(1), Status::Pass, "4".to_string()),
+                (1, Some(2), Status::Fail, "7".to_string()),
+                (2, Some(1), Status::Missing, "9".to_string()),
+                (2, Some(2), Status::Missing, "5".to_string()),
+                (3, None, Status::Missing, "no input".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_takes_parsing_off_the_part() {
+        use std::thread;
+
+        use crate::{
+            error::SolveError,
+            solution::{Answer, Solution},
+        };
+
+        // Parsing is all the work there is
+        struct SlowParse;
+
+        impl Solution for SlowParse {
+            fn parse(&self, _input: &str) -> Option<Result<(), SolveError>> {
+                thread::sleep(Duration::from_millis(20));
+                Some(Ok(()))
+            }
+
+            fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+                self.parse(input).unwrap()?;
+                Ok(Answer::from(0))
+            }
+        }
+
+        let day = Day {
+            year: 2021,
+            day: 1,
+            solution: &SlowParse,
+        };
+        let run = run_part(&day, 1, "", None).unwrap();
+        assert!(run.parse_time.unwrap() >= Duration::from_millis(20));
+        assert!(run.time.unwrap() < Duration::from_millis(10));
+    }
+
+    #[test]
+    fn it_renders_summaries() {
+        let runs = [
+            Run {
+                year: 2022,
+                day: 1,
+                part: Some(2),
+                status: Status::Fail,
+                expected: Some("5".to_string()),
+                answer: "7".to_string(),
+                time: Some(Duration::from_micros(1500)),
+                parse_time: Some(Duration::from_micros(200)),
+                input_hash: Some(input_hash("1\n2\n\n4")),
+            },
+            Run {
+                year: 2022,
+                day: 3,
+                part: None,
+                status: Status::Missing,
+                expected: None,
+                answer: "no input".to_string(),
+                time: None,
+                parse_time: None,
+                input_hash: None,
+            },
+        ];
+
+        assert_eq!(
+            render(&runs),
+            "| Year | Day | Part | Answer | Expected | Time | Status |\n\
+             | ---: | --: | ---: | :----- | :------- | ---: | :----- |\n\
+             | 2022 | 1 | 2 | 7 | 5 | 1.50ms | FAIL |\n\
+             | 2022 | 3 | - | no input | - | - | missing |\n"
+        );
+        assert_eq!(
+            render_json(&runs),
+            format!(
+                "{{\"year\":2022,\"day\":1,\"part\":2,\"status\":\"fail\",\"answer\":\"7\",\
+                 \"parse_ns\":200000,\"solve_ns\":1500000,\"input_hash\":\"{}\"}}\n",
+                input_hash("1\n2\n\n4")
+            )
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub answer: String,
+    pub time: Option<Duration>,
+    pub parse_time: Option<Duration>,
+    pub input_hash: Option<String>,
+}
+
+// Identifies the input a run used without publishing it
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+fn time_parse(day: &Day, input: &str) -> Option<Duration> {
+    let start = Instant::now();
+    // A parse error shows up again when solving, so only the time matters here
+    let _ = day.solution.parse(input)?;
+    Some(start.elapsed())
+}
+
+// Solutions parse their input again for each part, so the parse time is taken off the part's
+pub fn run_part(day: &Day, part: u8, input: &str, expected: Option<String>) -> Option<Run> {
+    let parse_time = time_parse(day, input);
+    let start = Instant::now();
+    let result = day.solve(part, input)?;
+    let time = start
+        .elapsed()
+        .saturating_sub(parse_time.unwrap_or_default());
+
+    let (status, answer) = match (result, &expected) {
+        (Err(err), _) => (Status::Error, err.to_string()),
+        (Ok(answer), None) => (Status::Missing, answer.to_string()),
+        (Ok(answer), Some(expected)) if answer.to_string() == *expected => {
+            (Status::Pass, answer.to_string())
+        }
+        (Ok(answer), Some(_)) => (Status::Fail, answer.to_string()),
+    };
+
+    Some(Run {
+        year: day.year,
+        day: day.day,
+        part: Some(part),
+        status,
+        expected,
+        answer,
+        time: Some(time),
+        parse_time,
+        input_hash: Some(input_hash(input)),
+    })
+}
+
+pub fn run_all(days: &[Day], store: &InputStore, ledgers: &[(u16, Ledger)]) -> Vec<Run> {
+    let expected = |day: &Day, part: u8| {
+        ledgers
+            .iter()
+            .find(|(year, _)| *year == day.year)
+            .and_then(|(_, ledger)| ledger.expected(day.day, PUZZLE_INPUT, part))
+            .map(str::to_string)
+    };
+
+    let inputs = days
+        .par_iter()
+        .map(|day| (day, store.load(day.year, day.day).ok()))
+        .collect::<Vec<_>>();
+
+    let jobs = inputs
+        .iter()
+        .flat_map(|(day, input)| match input {
+            Some(input) => vec![(*day, Some(input), Some(1)), (*day, Some(input), Some(2))],
+            None => vec![(*day, None, None)],
+        })
+        .collect::<Vec<_>>();
+
+    jobs.into_par_iter()
+        .filter_map(|(day, input, part)| {
+            let (Some(input), Some(part)) = (input, part) else {
+                return Some(Run {
+                    year: day.year,
+                    day: day.day,
+                    part: None,
+                    status: Status::Missing,
+                    expected: None,
+                    answer: "no input".to_string(),
+                    time: None,
+                    parse_time: None,
+                    input_hash: None,
+                });
+            };
+
+            run_part(day, part, input, expected(day, part))
+        })
+        .collect()
+}
+
+pub fn render(runs: &[Run]) -> String {
+    let mut table = String::new();
+
+    table.push_str("| Year | Day | Part | Answer | Expected | Time | Status |\n");
+    table.push_str("| ---: | --: | ---: | :----- | :------- | ---: | :----- |\n");
+
+    for run in runs {
+        writeln!(
+            table,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            run.year,
+            run.day,
+            run.part.map_or("-".to_string(), |part| part.to_string()),
+            cell(&run.answer),
+            cell(run.expected.as_deref().unwrap_or("-")),
+            run.time
+                .map_or("-".to_string(), |time| format!("{time:.2?}")),
+            run.status,
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    status: String,
+    answer: &'a str,
+    parse_ns: Option<u128>,
+    solve_ns: u128,
+    input_hash: &'a str,
+}
+
+// One JSON object per line for every part that was solved; days without an input are left out
+pub fn render_json(runs: &[Run]) -> String {
+    let mut lines = String::new();
+
+    for run in runs {
+        let (Some(part), Some(time), Some(input_hash)) = (run.part, run.time, &run.input_hash)
+        else {
+            continue;
+        };
+
+        let json = JsonRun {
+            year: run.year,
+            day: run.day,
+            part,
+            status: run.status.to_string().to_lowercase(),
+            answer: &run.answer,
+            parse_ns: run.parse_time.map(|time| time.as_nanos()),
+            solve_ns: time.as_nanos(),
+            input_hash,
+        };
+        writeln!(lines, "{}", serde_json::to_string(&json).unwrap()).unwrap();
+    }
+
+    lines
+}
@@ -0,0 +1,543 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: &str = "pub mod day_1;
+pub mod day_2;
+
+pub mod day_10;
+// pub mod day_15;
+// pub mod day_16;
+pub mod day_18;
+
+use crate::solution::Registry;
+
+pub const DAYS: Registry = &[
+    (1, &day_1::Solver),
+    (2, &day_2::Solver),
+    (10, &day_10::Solver),
+    (18, &day_18::Solver),
+];
+";
+
+    const LIB: &str = "pub mod error;
+pub mod solution;
+#[cfg(feature = \"y2021\")]
+pub mod year_2021;
+#[cfg(feature = \"y2023\")]
+pub mod year_2023;
+";
+
+    const SOLUTION: &str = "pub const YEARS: &[(u16, Registry)] = &[
+    #[cfg(feature = \"y2021\")]
+    (2021, crate::year_2021::DAYS),
+    #[cfg(feature = \"y2023\")]
+    (2023, crate::year_2023::DAYS),
+];
+";
+
+    const MANIFEST: &str = "[package]
+name = \"advent_of_rust\"
+
+[features]
+default = [\"y2021\", \"y2023\"]
+y2021 = []
+y2023 = []
+
+trace = []
+";
+
+    #[test]
+    fn it_inserts_modules_in_order() {
+        let source = insert_module(YEAR, "day_", 17);
+        assert!(source.contains("// pub mod day_16;\npub mod day_17;\npub mod day_18;\n"));
+
+        let source = insert_module(YEAR, "day_", 3);
+        assert!(source.contains("pub mod day_2;\npub mod day_3;\n\npub mod day_10;\n"));
+
+        let source = insert_module(
+            "pub mod day_1;\npub mod day_10;\npub mod day_2;\n",
+            "day_",
+            11,
+        );
+        assert_eq!(
+            source,
+            "pub mod day_1;\npub mod day_10;\npub mod day_11;\npub mod day_2;\n"
+        );
+    }
+
+    #[test]
+    fn it_inserts_year_modules() {
+        let source = insert_year_module(LIB, 2022);
+        assert!(source.contains(
+            "pub mod year_2021;\n#[cfg(feature = \"y2022\")]\npub mod year_2022;\n\
+             #[cfg(feature = \"y2023\")]\n"
+        ));
+        assert_eq!(insert_year_module(&source, 2022), source);
+
+        let source = insert_year_module(LIB, 2024);
+        assert!(source.ends_with("#[cfg(feature = \"y2024\")]\npub mod year_2024;\n"));
+    }
+
+    #[test]
+    fn it_uncomments_disabled_modules() {
+        let source = insert_module(YEAR, "day_", 15);
+        assert!(source.contains("\npub mod day_15;\n// pub mod day_16;\n"));
+        assert_eq!(insert_module(&source, "day_", 15), source);
+    }
+
+    #[test]
+    fn it_registers_days() {
+        let source = register_day(YEAR, 15).unwrap();
+        assert!(source.contains("    (10, &day_10::Solver),\n    (15, &day_15::Solver),\n"));
+        assert_eq!(register_day(&source, 15).unwrap(), source);
+
+        assert_eq!(
+            register_day("pub const DAYS: Registry = &[(1, &day_1::Solver)];\n", 2).unwrap(),
+            "pub const DAYS: Registry = &[(1, &day_1::Solver), (2, &day_2::Solver)];\n"
+        );
+        assert_eq!(register_day("pub mod day_1;\n", 2), None);
+    }
+
+    #[test]
+    fn it_registers_years() {
+        let source = register_year(SOLUTION, 2022).unwrap();
+        assert!(source.contains(
+            "    (2021, crate::year_2021::DAYS),\n    #[cfg(feature = \"y2022\")]\n\
+             \x20   (2022, crate::year_2022::DAYS),\n"
+        ));
+        assert_eq!(register_year(&source, 2022).unwrap(), source);
+        assert_eq!(register_year("pub mod year_2021;\n", 2022), None);
+    }
+
+    #[test]
+    fn it_adds_year_features() {
+        let source = add_year_feature(MANIFEST, 2022).unwrap();
+        assert!(source.contains(
+            "default = [\"y2021\", \"y2022\", \"y2023\"]\ny2021 = []\ny2022 = []\ny2023 = []\n"
+        ));
+        assert_eq!(add_year_feature(&source, 2022).unwrap(), source);
+
+        let source = add_year_feature(MANIFEST, 2020).unwrap();
+        assert!(source
+            .contains("default = [\"y2020\", \"y2021\", \"y2023\"]\ny2020 = []\ny2021 = []\n"));
+        assert_eq!(add_year_feature("[package]\n", 2022), None);
+    }
+
+    #[test]
+    fn it_scaffolds_days() {
+        let root = std::env::temp_dir().join("advent-of-rust-scaffold");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/solution")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/solution/mod.rs"), SOLUTION).unwrap();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+
+        let written = scaffold(&root, 2024, 1).unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .ends_with("pub mod year_2023;\n#[cfg(feature = \"y2024\")]\npub mod year_2024;\n"));
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("y2023 = []\ny2024 = []\n"));
+        assert_eq!(
+            fs::read_to_string(root.join("src/year_2024/mod.rs")).unwrap(),
+            year_template(1)
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/year_2024/day_1/mod.rs")).unwrap(),
+            day_template(2024, 1)
+        );
+        assert!(day_template(2024, 1).contains("test_input(2024, 1)"));
+
+        scaffold(&root, 2024, 2).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("src/year_2024/mod.rs")).unwrap(),
+            "pub mod day_1;\npub mod day_2;\n\nuse crate::solution::Registry;\n\n\
+             pub const DAYS: Registry = &[(1, &day_1::Solver), (2, &day_2::Solver)];\n"
+        );
+
+        assert!(matches!(
+            scaffold(&root, 2024, 2),
+            Err(ScaffoldError::Exists(_))
+        ));
+    }
+}
+
+const DAY_TEMPLATE: &str = r#"use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::test_input;
+
+    const EXAMPLE: &str = include_str!("example.in");
+
+    mod part1 {
+        use super::*;
+
+        #[test]
+        fn it_solves_example() {
+            let result = solve_part1(EXAMPLE);
+            assert_eq!(result, Ok(0));
+        }
+
+        #[test]
+        #[ignore]
+        fn it_solves_input() {
+            let input = test_input({year}, {day});
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(0));
+        }
+    }
+
+    mod part2 {
+        use super::*;
+
+        #[test]
+        fn it_solves_example() {
+            let result = solve_part2(EXAMPLE);
+            assert_eq!(result, Ok(0));
+        }
+
+        #[test]
+        #[ignore]
+        fn it_solves_input() {
+            let input = test_input({year}, {day});
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(0));
+        }
+    }
+}
+
+pub fn solve_part1(_input: &str) -> Result<u64, SolveError> {
+    Err(SolveError::no_solution("part 1 is not solved yet"))
+}
+
+pub fn solve_part2(_input: &str) -> Result<u64, SolveError> {
+    Err(SolveError::no_solution("part 2 is not solved yet"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
+"#;
+
+fn day_template(year: u16, day: u8) -> String {
+    DAY_TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+fn year_template(day: u8) -> String {
+    format!(
+        "pub mod day_{day};\n\nuse crate::solution::Registry;\n\n\
+         pub const DAYS: Registry = &[({day}, &day_{day}::Solver)];\n"
+    )
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Io(PathBuf, io::Error),
+    Malformed(PathBuf),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            ScaffoldError::Malformed(path) => {
+                write!(f, "{}: could not find the registry", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")?.strip_suffix(';')
+}
+
+fn module_number(line: &str, prefix: &str) -> Option<(u32, bool)> {
+    let (line, commented) = match line.strip_prefix("// ") {
+        Some(line) => (line, true),
+        None => (line, false),
+    };
+    let number = module_name(line)?.strip_prefix(prefix)?.parse().ok()?;
+
+    Some((number, commented))
+}
+
+fn insert_module(source: &str, prefix: &str, number: u32) -> String {
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let name = format!("{prefix}{number}");
+    let numbers = lines
+        .iter()
+        .map(|line| module_number(line, prefix))
+        .collect::<Vec<_>>();
+
+    if let Some(i) = numbers.iter().position(|&n| n == Some((number, true))) {
+        lines[i] = format!("pub mod {name};");
+    } else if !numbers.contains(&Some((number, false))) {
+        let previous = numbers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, n)| Some((n.filter(|&(n, _)| n < number)?.0, i)))
+            .max();
+        let mut i = match previous {
+            Some((_, i)) => i + 1,
+            None => lines
+                .iter()
+                .position(|line| module_name(line).is_some())
+                .unwrap_or(0),
+        };
+
+        // rustfmt sorts each block of declarations by name, so day_10 goes before day_2
+        while i > 0 && module_name(&lines[i - 1]).is_some_and(|other| other > name.as_str()) {
+            i -= 1;
+        }
+        while i < lines.len() && module_name(&lines[i]).is_some_and(|other| other < name.as_str()) {
+            i += 1;
+        }
+
+        lines.insert(i, format!("pub mod {name};"));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn register(
+    source: &str,
+    head: &str,
+    number: u32,
+    entry: impl Fn(u32) -> String,
+) -> Option<String> {
+    let start = source.find(head)?;
+    let end = start + source[start..].find("];")? + 2;
+
+    let mut numbers = source[start + head.len()..end]
+        .split('(')
+        .skip(1)
+        .map(|entry| entry.split(',').next()?.trim().parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    if !numbers.contains(&number) {
+        numbers.push(number);
+        numbers.sort();
+    }
+
+    let entries = numbers.into_iter().map(entry).collect::<Vec<_>>();
+    let inline = format!("[{}]", entries.join(", "));
+    // Mirrors rustfmt's array width limit
+    let list = if inline.len() < 60 {
+        format!("{head}{inline};")
+    } else {
+        let entries = entries
+            .iter()
+            .map(|entry| format!("    {entry},\n"))
+            .collect::<String>();
+        format!("{head}[\n{entries}];")
+    };
+
+    Some(format!("{}{list}{}", &source[..start], &source[end..]))
+}
+
+fn register_day(source: &str, day: u8) -> Option<String> {
+    register(source, "pub const DAYS: Registry = &", day.into(), |day| {
+        format!("({day}, &day_{day}::Solver)")
+    })
+}
+
+fn year_feature(year: u32) -> String {
+    format!("y{year}")
+}
+
+// Year modules each sit under their feature's `#[cfg]`, which moves along with them
+fn insert_year_module(source: &str, year: u32) -> String {
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let years = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((module_number(line, "year_")?.0, i)))
+        .collect::<Vec<_>>();
+
+    if years.iter().any(|&(n, _)| n == year) {
+        return source.to_string();
+    }
+
+    let i = match years.iter().find(|&&(n, _)| n > year) {
+        Some(&(_, i)) if i > 0 && lines[i - 1].starts_with("#[cfg(") => i - 1,
+        Some(&(_, i)) => i,
+        None => years.last().map_or(lines.len(), |&(_, i)| i + 1),
+    };
+    lines.splice(
+        i..i,
+        [
+            format!("#[cfg(feature = \"{}\")]", year_feature(year)),
+            format!("pub mod year_{year};"),
+        ],
+    );
+
+    lines.join("\n") + "\n"
+}
+
+fn register_year(source: &str, year: u16) -> Option<String> {
+    let head = "pub const YEARS: &[(u16, Registry)] = &[";
+    let start = source.find(head)?;
+    let end = start + source[start..].find("];")? + 2;
+
+    let mut years = source[start..end]
+        .split("crate::year_")
+        .skip(1)
+        .map(|entry| entry.split("::").next()?.parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    if !years.contains(&year.into()) {
+        years.push(year.into());
+        years.sort();
+    }
+
+    let entries = years
+        .into_iter()
+        .map(|year| {
+            format!(
+                "    #[cfg(feature = \"{}\")]\n    ({year}, crate::year_{year}::DAYS),\n",
+                year_feature(year)
+            )
+        })
+        .collect::<String>();
+
+    Some(format!(
+        "{}{head}\n{entries}];{}",
+        &source[..start],
+        &source[end..]
+    ))
+}
+
+// Adds the year's feature to Cargo.toml, next to the other years and on by default
+fn add_year_feature(source: &str, year: u16) -> Option<String> {
+    let feature = year_feature(year.into());
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let default = lines
+        .iter()
+        .position(|line| line.starts_with("default = ["))?;
+    let mut features = lines[default]
+        .strip_prefix("default = [")?
+        .strip_suffix(']')?
+        .split(',')
+        .map(|feature| feature.trim().trim_matches('"').to_string())
+        .filter(|feature| !feature.is_empty())
+        .collect::<Vec<_>>();
+
+    if !features.contains(&feature) {
+        features.push(feature.clone());
+        features.sort();
+    }
+    lines[default] = format!(
+        "default = [{}]",
+        features
+            .iter()
+            .map(|feature| format!("{feature:?}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let declaration = format!("{feature} = []");
+    if !lines.contains(&declaration) {
+        let is_year = |line: &str| {
+            line.strip_suffix(" = []")
+                .and_then(|name| name.strip_prefix('y'))
+                .is_some_and(|year| year.parse::<u32>().is_ok())
+        };
+        let i = lines
+            .iter()
+            .rposition(|line| is_year(line) && *line < declaration)
+            .map_or(default + 1, |i| i + 1);
+        lines.insert(i, declaration);
+    }
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+fn write(path: PathBuf, contents: &str, written: &mut Vec<PathBuf>) -> Result<(), ScaffoldError> {
+    fs::write(&path, contents).map_err(|err| ScaffoldError::Io(path.clone(), err))?;
+    written.push(path);
+    Ok(())
+}
+
+fn create_dir(path: &Path) -> Result<(), ScaffoldError> {
+    fs::create_dir_all(path).map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = root.join("src");
+    let year_dir = src.join(format!("year_{year}"));
+    let day_dir = year_dir.join(format!("day_{day}"));
+    let mut written = vec![];
+
+    if day_dir.exists() {
+        return Err(ScaffoldError::Exists(day_dir));
+    }
+
+    let year_mod = year_dir.join("mod.rs");
+
+    if year_mod.exists() {
+        let source = insert_module(&read(&year_mod)?, "day_", day.into());
+        let source =
+            register_day(&source, day).ok_or_else(|| ScaffoldError::Malformed(year_mod.clone()))?;
+        write(year_mod, &source, &mut written)?;
+    } else {
+        let lib = src.join("lib.rs");
+        let solution = src.join("solution").join("mod.rs");
+        let manifest = root.join("Cargo.toml");
+
+        let lib_source = insert_year_module(&read(&lib)?, year.into());
+        let solution_source = register_year(&read(&solution)?, year)
+            .ok_or_else(|| ScaffoldError::Malformed(solution.clone()))?;
+        let manifest_source = add_year_feature(&read(&manifest)?, year)
+            .ok_or_else(|| ScaffoldError::Malformed(manifest.clone()))?;
+
+        create_dir(&year_dir)?;
+        write(year_mod, &year_template(day), &mut written)?;
+        write(lib, &lib_source, &mut written)?;
+        write(solution, &solution_source, &mut written)?;
+        write(manifest, &manifest_source, &mut written)?;
+    }
+
+    create_dir(&day_dir)?;
+    write(
+        day_dir.join("mod.rs"),
+        &day_template(year, day),
+        &mut written,
+    )?;
+    write(day_dir.join("example.in"), "", &mut written)?;
+
+    Ok(written)
+}
@@ -0,0 +1,293 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use num::Zero;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -1- b -1- d, a -5- c -1- d, d -1- e
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('a', 1), ('d', 1)],
+            'c' => vec![('a', 5), ('d', 1)],
+            'd' => vec![('b', 1), ('c', 1), ('e', 1)],
+            _ => vec![('d', 1)],
+        }
+    }
+
+    fn neighbors(node: &char) -> Vec<char> {
+        edges(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn it_searches_breadth_first() {
+        let path = bfs('a', neighbors, |&node| node == 'e').unwrap();
+
+        assert_eq!(path.states, ['a', 'b', 'd', 'e']);
+        assert_eq!(path.cost, 3);
+        assert_eq!(bfs('a', neighbors, |&node| node == 'z'), None);
+    }
+
+    #[test]
+    fn it_finds_cheapest_paths() {
+        let path = dijkstra('c', edges, |&node| node == 'a').unwrap();
+
+        assert_eq!(path.states, ['c', 'd', 'b', 'a']);
+        assert_eq!(path.cost, 3);
+    }
+
+    #[test]
+    fn it_follows_heuristics() {
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 3 && y <= 3 && (x, y) != (1, 1))
+                .map(|pos| (pos, 1))
+        };
+        let remaining = |&(x, y): &(i32, i32)| (3 - x) + (3 - y);
+
+        let path = astar((0, 0), successors, remaining, |&pos| pos == (3, 3)).unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states.len(), 7);
+        assert!(!path.states.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn it_measures_distances() {
+        let distances = distances('a', neighbors, 2);
+
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&'d'], 2);
+        assert!(!distances.contains_key(&'e'));
+    }
+
+    #[test]
+    fn it_finds_longest_paths() {
+        let path = longest_path('a', edges, |&node| node == 'e', 10).unwrap();
+
+        assert_eq!(path.states, ['a', 'c', 'd', 'e']);
+        assert_eq!(path.cost, 7);
+        assert_eq!(
+            longest_path('a', edges, |&node| node == 'e', 2).map(|path| path.cost),
+            None
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+fn unwind<S: Clone>(goal: S, mut parent: impl FnMut(&S) -> Option<S>) -> Vec<S> {
+    let mut states = vec![goal];
+
+    while let Some(prev) = parent(states.last().unwrap()) {
+        states.push(prev);
+    }
+
+    states.reverse();
+    states
+}
+
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut q = VecDeque::from([start]);
+
+    while let Some(state) = q.pop_front() {
+        if is_goal(&state) {
+            let states = unwind(state, |state| parents[state].clone());
+            return Some(Path {
+                cost: states.len() - 1,
+                states,
+            });
+        }
+
+        for next in successors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                q.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+// Every state reachable within `limit` steps, with its distance from the start
+pub fn distances<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    limit: usize,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut q = VecDeque::from([(start, 0)]);
+
+    while let Some((state, distance)) = q.pop_front() {
+        if distance == limit {
+            continue;
+        }
+
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                q.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+// `heuristic` must never overestimate the remaining cost, or the path found may not be the cheapest
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // States live in `queued` so the heap only has to order costs and indices
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), 0))]);
+    let mut best = HashMap::from([(start.clone(), (C::zero(), None))]);
+    let mut queued = vec![start];
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        let state = queued[i].clone();
+
+        if cost > best[&state].0 {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(Path {
+                states: unwind(state, |state| best[state].1.clone()),
+                cost,
+            });
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+
+            if best
+                .get(&next)
+                .is_some_and(|&(known, _)| known <= next_cost)
+            {
+                continue;
+            }
+
+            best.insert(next.clone(), (next_cost, Some(state.clone())));
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                queued.len(),
+            )));
+            queued.push(next);
+        }
+    }
+
+    None
+}
+
+// Exhaustive over simple paths (no state visited twice) of at most `max_depth` steps
+pub fn longest_path<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    max_depth: usize,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    struct Search<'a, S, C, I> {
+        successors: &'a mut dyn FnMut(&S) -> I,
+        is_goal: &'a mut dyn FnMut(&S) -> bool,
+        max_depth: usize,
+        path: Vec<S>,
+        on_path: HashSet<S>,
+        best: Option<Path<S, C>>,
+    }
+
+    impl<S, C, I> Search<'_, S, C, I>
+    where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Zero,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        fn visit(&mut self, cost: C) {
+            let state = self.path.last().unwrap().clone();
+
+            if (self.is_goal)(&state) {
+                if self.best.as_ref().is_none_or(|best| cost > best.cost) {
+                    self.best = Some(Path {
+                        states: self.path.clone(),
+                        cost,
+                    });
+                }
+                return;
+            }
+            if self.path.len() > self.max_depth {
+                return;
+            }
+
+            for (next, step) in (self.successors)(&state) {
+                if self.on_path.insert(next.clone()) {
+                    self.path.push(next.clone());
+                    self.visit(cost + step);
+                    self.path.pop();
+                    self.on_path.remove(&next);
+                }
+            }
+        }
+    }
+
+    let mut search = Search {
+        successors: &mut successors,
+        is_goal: &mut is_goal,
+        max_depth,
+        on_path: HashSet::from([start.clone()]),
+        path: vec![start],
+        best: None,
+    };
+    search.visit(C::zero());
+
+    search.best
+}
@@ -0,0 +1,168 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{dot::Dot, error::SolveError};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_answers() {
+        assert_eq!(Answer::from(42u32), Answer::Int(42));
+        assert_eq!(Answer::from(-7i64), Answer::Int(-7));
+        assert_eq!(Answer::from("CMZ"), Answer::Str("CMZ".to_string()));
+        assert_eq!(
+            Answer::try_from(u128::from(u64::MAX)).map(|answer| answer.to_string()),
+            Ok(u64::MAX.to_string())
+        );
+        assert_eq!(
+            Answer::try_from(u128::MAX),
+            Err(SolveError::no_solution(format!(
+                "answer {} does not fit in an i128",
+                u128::MAX
+            )))
+        );
+    }
+
+    #[cfg(feature = "y2022")]
+    #[test]
+    fn it_finds_registered_days() {
+        let day = find(2022, 1).unwrap();
+        assert_eq!((day.year, day.day), (2022, 1));
+        assert_eq!(day.solve(1, "1\n2\n\n4"), Some(Ok(Answer::Int(4))));
+        assert!(matches!(
+            day.solve(1, "1\nx"),
+            Some(Err(SolveError::Parse { line: 2, .. }))
+        ));
+        assert!(find(2022, 26).is_none());
+    }
+
+    #[test]
+    fn it_lists_days_in_order() {
+        let days = days().map(|day| (day.year, day.day)).collect::<Vec<_>>();
+        let mut sorted = days.clone();
+        sorted.sort();
+        assert_eq!(days, sorted);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(i128::try_from(value).expect("answer does not fit in an i128"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, u32, i64, u64, usize);
+
+impl TryFrom<u128> for Answer {
+    type Error = SolveError;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        i128::try_from(value)
+            .map(Answer::Int)
+            .map_err(|_| SolveError::no_solution(format!("answer {value} does not fit in an i128")))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+pub trait Solution: Sync {
+    fn parse(&self, _input: &str) -> Option<Result<(), SolveError>> {
+        None
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    fn part2(&self, _input: &str) -> Option<Result<Answer, SolveError>> {
+        None
+    }
+
+    // The parsed input as a Graphviz graph, for puzzles shaped like one
+    fn dot(&self, _input: &str) -> Option<Result<Dot, SolveError>> {
+        None
+    }
+}
+
+pub type Registry = &'static [(u8, &'static dyn Solution)];
+
+// Each year is behind its own `yYYYY` feature, so only the enabled ones are listed
+pub const YEARS: &[(u16, Registry)] = &[
+    #[cfg(feature = "y2021")]
+    (2021, crate::year_2021::DAYS),
+    #[cfg(feature = "y2022")]
+    (2022, crate::year_2022::DAYS),
+    #[cfg(feature = "y2023")]
+    (2023, crate::year_2023::DAYS),
+];
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn Solution,
+}
+
+impl Day {
+    pub fn solve(&self, part: u8, input: &str) -> Option<Result<Answer, SolveError>> {
+        match part {
+            1 => Some(self.solution.part1(input)),
+            2 => self.solution.part2(input),
+            _ => None,
+        }
+    }
+}
+
+pub fn days() -> impl Iterator<Item = Day> {
+    YEARS.iter().flat_map(|&(year, days)| {
+        days.iter().map(move |&(day, solution)| Day {
+            year,
+            day,
+            solution,
+        })
+    })
+}
+
+pub fn find(year: u16, day: u8) -> Option<Day> {
+    days().find(|d| d.year == year && d.day == day)
+}
+
+pub fn input_path(inputs: &Path, year: u16, day: u8, input: &str) -> PathBuf {
+    inputs
+        .join(format!("year_{year}"))
+        .join(format!("day_{day}"))
+        .join(input)
+}
@@ -0,0 +1,431 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Client, HttpError},
+    inputs,
+    solution::Answer,
+};
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::client::serve;
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+        high.  If you're stuck, make sure you're using the full input data.  Please wait one \
+        minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are one gold star \
+        closer to saving your vacation.</p></article></main>";
+    const TOO_RECENTLY: &str = "<main><article><p>You gave an answer too recently; you have to \
+        wait after submitting an answer before trying again.  You have 1m 5s left to wait. \
+        </p></article></main>";
+
+    fn submitter(name: &str, url: &str) -> Submitter {
+        let dir = env::temp_dir().join(format!("advent-of-rust-submit-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        Submitter::new(
+            dir,
+            Client::default().with_base_url(url).with_session("abc"),
+        )
+    }
+
+    #[test]
+    fn it_parses_responses() {
+        assert_eq!(Outcome::parse(CORRECT), Some(Outcome::Correct));
+        assert_eq!(Outcome::parse(TOO_HIGH), Some(Outcome::TooHigh));
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too low."),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer.  If you're stuck..."),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::parse(TOO_RECENTLY),
+            Some(Outcome::Wait(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Outcome::AlreadySolved)
+        );
+        assert_eq!(Outcome::parse("<html>Log in</html>"), None);
+    }
+
+    #[test]
+    fn it_remembers_wrong_guesses_and_bounds() {
+        let mut history = History::default();
+        history.record(&Answer::Int(100), &Outcome::TooHigh);
+        history.record(&Answer::Int(10), &Outcome::TooLow);
+        history.record(&Answer::Int(50), &Outcome::Wrong);
+
+        assert!(matches!(
+            history.check(&Answer::Int(50), 0),
+            Err(SubmitError::KnownWrong(_))
+        ));
+        assert!(matches!(
+            history.check(&Answer::Int(100), 0),
+            Err(SubmitError::KnownWrong(_))
+        ));
+        assert!(matches!(
+            history.check(&Answer::Int(150), 0),
+            Err(SubmitError::OutOfBounds {
+                low: Some(10),
+                high: Some(100)
+            })
+        ));
+        assert!(matches!(
+            history.check(&Answer::Int(3), 0),
+            Err(SubmitError::OutOfBounds { .. })
+        ));
+        assert!(history.check(&Answer::Int(42), 0).is_ok());
+
+        history.record(&Answer::from("ABC"), &Outcome::Wrong);
+        assert!(matches!(
+            history.check(&Answer::from("ABC"), 0),
+            Err(SubmitError::KnownWrong(_))
+        ));
+        assert!(history.check(&Answer::from("ABD"), 0).is_ok());
+    }
+
+    #[test]
+    fn it_submits_answers() {
+        let (url, server) = serve("200 OK", TOO_HIGH);
+        let submitter = submitter("submit", &url);
+
+        assert_eq!(
+            submitter.submit(2022, 1, 1, &Answer::Int(42)),
+            Ok(Outcome::TooHigh)
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("\n\nlevel=1&answer=42"));
+
+        let history = submitter.history(2022, 1, 1).unwrap();
+        assert_eq!(history.wrong, ["42"]);
+        assert_eq!(history.high, Some(42));
+        assert!(history.wait_until.is_some());
+
+        // No server is listening anymore, so these have to be refused locally
+        assert!(matches!(
+            submitter.submit(2022, 1, 1, &Answer::Int(41)),
+            Err(SubmitError::TooSoon(_))
+        ));
+        assert_eq!(submitter.history(2022, 1, 2).unwrap(), History::default());
+    }
+
+    #[test]
+    fn it_refuses_to_resubmit_solved_parts() {
+        let (url, server) = serve("200 OK", CORRECT);
+        let submitter = submitter("solved", &url);
+
+        assert_eq!(
+            submitter.submit(2022, 1, 2, &Answer::Int(7)),
+            Ok(Outcome::Correct)
+        );
+        server.join().unwrap();
+
+        assert_eq!(
+            submitter.submit(2022, 1, 2, &Answer::Int(8)),
+            Err(SubmitError::AlreadySolved("7".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_waits_when_rate_limited() {
+        let (url, server) = serve("200 OK", TOO_RECENTLY);
+        let submitter = submitter("rate-limit", &url);
+
+        assert_eq!(
+            submitter.submit(2022, 1, 1, &Answer::Int(7)),
+            Ok(Outcome::Wait(Duration::from_secs(65)))
+        );
+        server.join().unwrap();
+
+        let history = submitter.history(2022, 1, 1).unwrap();
+        assert!(history.wrong.is_empty());
+        assert!(matches!(
+            history.check(&Answer::Int(7), now()),
+            Err(SubmitError::TooSoon(_))
+        ));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(wait) => write!(f, "answered too recently, wait {}s", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+fn parse_duration(text: &str) -> Option<Duration> {
+    text.split_whitespace()
+        .map(|part| {
+            let (n, unit) = part.split_at(part.len().checked_sub(1)?);
+            let n = n.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(n * 60 * 60),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+// Wrong answers come with a cooldown, e.g. "Please wait one minute before trying again"
+fn parse_cooldown(response: &str) -> Option<Duration> {
+    let (_, rest) = response.split_once("lease wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+    let minutes = match amount.split_once(' ')? {
+        ("one", _) => 1,
+        (n, _) => n.parse().ok()?,
+    };
+
+    Some(Duration::from_secs(minutes * 60))
+}
+
+impl Outcome {
+    pub fn parse(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if response.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if response.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else if response.contains("That's not the right answer") {
+            Some(Outcome::Wrong)
+        } else if response.contains("You gave an answer too recently") {
+            let (_, rest) = response.split_once("You have ")?;
+            let (wait, _) = rest.split_once(" left to wait")?;
+            Some(Outcome::Wait(parse_duration(wait)?))
+        } else if response.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitError {
+    AlreadySolved(String),
+    KnownWrong(String),
+    OutOfBounds { low: Option<i64>, high: Option<i64> },
+    TooSoon(Duration),
+    UnknownResponse,
+    Http(HttpError),
+    Io(PathBuf, String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::AlreadySolved(answer) => write!(f, "already solved with {answer}"),
+            SubmitError::KnownWrong(answer) => write!(f, "{answer} was already rejected"),
+            SubmitError::OutOfBounds { low, high } => {
+                write!(f, "the answer must be")?;
+                if let Some(low) = low {
+                    write!(f, " above {low}")?;
+                }
+                if let (Some(_), Some(_)) = (low, high) {
+                    write!(f, " and")?;
+                }
+                if let Some(high) = high {
+                    write!(f, " below {high}")?;
+                }
+                Ok(())
+            }
+            SubmitError::TooSoon(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            SubmitError::UnknownResponse => write!(f, "could not understand the response"),
+            SubmitError::Http(err) => write!(f, "{err}"),
+            SubmitError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct History {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+fn as_int(answer: &Answer) -> Option<i64> {
+    match answer {
+        Answer::Int(n) => i64::try_from(*n).ok(),
+        Answer::Str(_) => None,
+    }
+}
+
+impl History {
+    pub fn check(&self, answer: &Answer, now: u64) -> Result<(), SubmitError> {
+        let text = answer.to_string();
+
+        if let Some(correct) = &self.correct {
+            return Err(SubmitError::AlreadySolved(correct.clone()));
+        }
+        if self.wrong.contains(&text) {
+            return Err(SubmitError::KnownWrong(text));
+        }
+        if let Some(n) = as_int(answer) {
+            if self.low.is_some_and(|low| n <= low) || self.high.is_some_and(|high| n >= high) {
+                return Err(SubmitError::OutOfBounds {
+                    low: self.low,
+                    high: self.high,
+                });
+            }
+        }
+        if let Some(wait_until) = self.wait_until.filter(|&wait_until| wait_until > now) {
+            return Err(SubmitError::TooSoon(Duration::from_secs(wait_until - now)));
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, answer: &Answer, outcome: &Outcome) {
+        let text = answer.to_string();
+
+        match outcome {
+            Outcome::Correct => self.correct = Some(text),
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong => self.wrong.push(text),
+            Outcome::Wait(_) | Outcome::AlreadySolved => {}
+        }
+
+        match (outcome, as_int(answer)) {
+            (Outcome::TooHigh, Some(n)) => {
+                self.high = Some(self.high.map_or(n, |high| high.min(n)))
+            }
+            (Outcome::TooLow, Some(n)) => self.low = Some(self.low.map_or(n, |low| low.max(n))),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    dir: PathBuf,
+    client: Client,
+}
+
+impl Submitter {
+    pub fn new(dir: impl Into<PathBuf>, client: Client) -> Self {
+        Submitter {
+            dir: dir.into(),
+            client,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Submitter::new(inputs::default_dir(), Client::from_env())
+    }
+
+    pub fn path(&self, year: u16, day: u8, part: u8) -> PathBuf {
+        self.dir
+            .join(self.client.session_key())
+            .join(format!("year_{year}"))
+            .join(format!("day_{day}.part{part}.toml"))
+    }
+
+    pub fn history(&self, year: u16, day: u8, part: u8) -> Result<History, SubmitError> {
+        let path = self.path(year, day, part);
+
+        match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text).map_err(|err| SubmitError::Io(path, err.to_string())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(SubmitError::Io(path, err.to_string())),
+        }
+    }
+
+    fn save(&self, path: &Path, history: &History) -> Result<(), SubmitError> {
+        let write = || -> io::Result<()> {
+            let text = toml::to_string(history).map_err(io::Error::other)?;
+            fs::create_dir_all(path.parent().unwrap_or(path))?;
+            fs::write(path, text)
+        };
+
+        write().map_err(|err| SubmitError::Io(path.to_path_buf(), err.to_string()))
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+    ) -> Result<Outcome, SubmitError> {
+        let now = now();
+        let mut history = self.history(year, day, part)?;
+        history.check(answer, now)?;
+
+        let response = self
+            .client
+            .post(
+                &format!("/{year}/day/{day}/answer"),
+                &[
+                    ("level", &part.to_string()),
+                    ("answer", &answer.to_string()),
+                ],
+            )
+            .map_err(SubmitError::Http)?;
+        let outcome = Outcome::parse(&response).ok_or(SubmitError::UnknownResponse)?;
+
+        history.record(answer, &outcome);
+
+        let wait = match outcome {
+            Outcome::Wait(wait) => Some(wait),
+            _ => parse_cooldown(&response),
+        };
+        if let Some(wait) = wait {
+            history.wait_until = Some(now + wait.as_secs());
+        }
+
+        self.save(&self.path(year, day, part), &history)?;
+
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,220 @@
+use std::{cell::RefCell, fmt::Debug, io, rc::Rc};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_records_events() {
+        let ((), events) = record(|| {
+            emit(Event {
+                name: "pulse sent",
+                fields: vec![("from", "a".to_string()), ("to", "b".to_string())],
+            })
+        });
+
+        assert_eq!(
+            events,
+            [Event {
+                name: "pulse sent",
+                fields: vec![("from", "a".to_string()), ("to", "b".to_string())],
+            }]
+        );
+        assert!(!is_enabled());
+    }
+
+    #[test]
+    fn it_drops_events_without_a_sink() {
+        emit(Event {
+            name: "ignored",
+            fields: vec![],
+        });
+        let (n, events) = record(|| 42);
+
+        assert_eq!(n, 42);
+        assert!(events.is_empty());
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn it_traces_through_the_macro() {
+        let x = 3;
+        let ((), events) = record(|| crate::trace_event!("rock moved", x, y = x + 1));
+
+        assert_eq!(
+            events[0].fields,
+            [("x", "3".to_string()), ("y", "4".to_string())]
+        );
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn it_writes_strings_without_quotes() {
+        let from = "button";
+        let ((), events) = record(|| {
+            crate::trace_event!(
+                "pulse sent",
+                from,
+                to = "broad\"caster".to_string(),
+                pair = ("a", 1)
+            )
+        });
+
+        assert_eq!(
+            events[0].fields,
+            [
+                ("from", "button".to_string()),
+                ("to", "broad\"caster".to_string()),
+                ("pair", "(\"a\", 1)".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn it_writes_json_lines() {
+        let event = Event {
+            name: "sand settled",
+            fields: vec![("x", "500".to_string())],
+        };
+        let mut out = vec![];
+        write_json(&mut out, 2022, 14, 1, &event).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"year\":2022,\"day\":14,\"part\":1,\"event\":\"sand settled\",\"fields\":{\"x\":\"500\"}}\n"
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+type Sink = Box<dyn FnMut(Event)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+// Lets `trace_event!` skip formatting its fields when nobody is listening
+pub fn is_enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+pub fn emit(event: Event) {
+    SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            sink(event);
+        }
+    });
+}
+
+// Sends every event emitted on this thread while `f` runs to `sink`
+pub fn with_sink<T>(sink: impl FnMut(Event) + 'static, f: impl FnOnce() -> T) -> T {
+    let previous = SINK.with(|s| s.replace(Some(Box::new(sink))));
+    let result = f();
+    SINK.with(|s| s.replace(previous));
+    result
+}
+
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let events = Rc::new(RefCell::new(vec![]));
+    let sink = events.clone();
+    let result = with_sink(move |event| sink.borrow_mut().push(event), f);
+
+    (result, events.take())
+}
+
+#[derive(Serialize)]
+struct JsonEvent<'a> {
+    year: u16,
+    day: u8,
+    part: u8,
+    event: &'a str,
+    fields: Map<String, Value>,
+}
+
+// One JSON object per line; field names end up sorted
+pub fn write_json(
+    out: &mut impl io::Write,
+    year: u16,
+    day: u8,
+    part: u8,
+    event: &Event,
+) -> io::Result<()> {
+    let json = JsonEvent {
+        year,
+        day,
+        part,
+        event: event.name,
+        fields: event
+            .fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), Value::from(value.as_str())))
+            .collect(),
+    };
+    writeln!(out, "{}", serde_json::to_string(&json)?)
+}
+
+// Lets `trace_event!` write strings as they are and everything else with `Debug`: method
+// lookup tries `Field<T>` before `&Field<T>`, so the string impls win where they apply.
+pub struct Field<'a, T: ?Sized>(pub &'a T);
+
+pub trait StrField {
+    fn render(&self) -> String;
+}
+
+impl StrField for Field<'_, &str> {
+    fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StrField for Field<'_, String> {
+    fn render(&self) -> String {
+        self.0.clone()
+    }
+}
+
+pub trait DebugField {
+    fn render(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> DebugField for &Field<'_, T> {
+    fn render(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+// Emits an event with `Debug`-formatted fields, apart from strings, which are written as they
+// are, e.g. `trace_event!("pulse sent", from, to = name)`. Without the `trace` feature this
+// expands to nothing, so solvers don't pay for it.
+#[macro_export]
+macro_rules! trace_event {
+    ($name:expr $(, $key:ident $(= $value:expr)?)* $(,)?) => {{
+        #[cfg(feature = "trace")]
+        if $crate::trace::is_enabled() {
+            #[allow(unused_imports)]
+            use $crate::trace::{DebugField as _, StrField as _};
+            $crate::trace::emit($crate::trace::Event {
+                name: $name,
+                fields: vec![$((
+                    stringify!($key),
+                    (&$crate::trace::Field(&$crate::trace_event!(@value $key $(= $value)?)))
+                        .render(),
+                )),*],
+            });
+        }
+    }};
+    (@value $key:ident) => {
+        $key
+    };
+    (@value $key:ident = $value:expr) => {
+        $value
+    };
+}
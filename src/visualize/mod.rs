@@ -0,0 +1,384 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Write as _},
+    io::{self, Write},
+    rc::Rc,
+};
+
+use crate::{geometry::Point2, grid::Grid};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(art: &str) -> Frame {
+        Frame::new(Grid::parse(art, Some).unwrap())
+    }
+
+    #[test]
+    fn it_records_every_nth_frame() {
+        let ((), frames) = record(3, || {
+            for i in 0..7 {
+                super::frame(|| Frame::new(Grid::filled(1, 1, char::from(b'0' + i))));
+            }
+        });
+
+        assert_eq!(frames, [frame("0"), frame("3"), frame("6")]);
+        assert!(!is_enabled());
+    }
+
+    #[test]
+    fn it_skips_drawing_without_a_sink() {
+        super::frame(|| unreachable!("nobody is watching"));
+    }
+
+    #[test]
+    fn it_draws_sparse_frames() {
+        let frame = Frame::sparse([(Point2::new(-1, 2), '#'), (Point2::new(1, 3), 'o')], '.');
+
+        assert_eq!(frame.origin, Point2::new(-1, 2));
+        assert_eq!(frame.to_string(), "#..\n..o\n");
+        assert_eq!(frame.extent(), (Point2::new(-1, 2), Point2::new(2, 4)));
+    }
+
+    #[test]
+    fn it_colours_frames_for_terminals() {
+        assert_eq!(
+            ansi(&frame("#.\n..")),
+            "\x1b[38;2;190;190;200m#\x1b[38;2;40;40;52m.\x1b[0m\n\
+             \x1b[38;2;40;40;52m..\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn it_spans_every_frame() {
+        let frames = [
+            frame("..\n.."),
+            Frame::sparse([(Point2::new(-2, 1), '#'), (Point2::new(0, 3), '#')], '.'),
+        ];
+
+        assert_eq!(
+            Extent::of(&frames),
+            Some(Extent {
+                min: Point2::new(-2, 0),
+                max: Point2::new(2, 4)
+            })
+        );
+        assert_eq!(Extent::of(&[]), None);
+    }
+
+    #[cfg(feature = "visualize")]
+    #[test]
+    fn it_writes_images() {
+        let frames = [frame("#.\n.o"), frame(".#\no.")];
+
+        let mut png = vec![];
+        write_png(&mut png, &frames[0]).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let mut gif = vec![];
+        let mut writer = GifWriter::new(&mut gif, Extent::of(&frames).unwrap(), 10).unwrap();
+        for frame in &frames {
+            writer.write(frame).unwrap();
+        }
+        drop(writer);
+        assert!(gif.starts_with(b"GIF89a"));
+    }
+}
+
+// A picture of a simulation's state, one character per cell. `origin` is where the top left
+// cell sits in the puzzle's coordinates, so frames of a growing state still line up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub origin: Point2,
+    pub cells: Grid<char>,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<char>) -> Self {
+        Frame {
+            origin: Point2::new(0, 0),
+            cells,
+        }
+    }
+
+    // Draws `cells` over `background`, cropped to their bounding box
+    pub fn sparse(cells: impl IntoIterator<Item = (Point2, char)>, background: char) -> Self {
+        let cells = cells.into_iter().collect::<Vec<_>>();
+        let Some(extent) = Extent::around(cells.iter().map(|&(pos, _)| pos)) else {
+            return Frame::new(Grid::filled(0, 0, background));
+        };
+
+        let size = extent.max - extent.min;
+        let mut grid = Grid::filled(size.x as usize, size.y as usize, background);
+        for (pos, c) in cells {
+            let offset = pos - extent.min;
+            grid[(offset.x as usize, offset.y as usize)] = c;
+        }
+
+        Frame {
+            origin: extent.min,
+            cells: grid,
+        }
+    }
+
+    // The top left corner and the corner just past the bottom right one
+    pub fn extent(&self) -> (Point2, Point2) {
+        let size = Point2::new(self.cells.width() as i64, self.cells.height() as i64);
+        (self.origin, self.origin + size)
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+// The area covered by a set of frames, with `max` exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Extent {
+    fn around(points: impl IntoIterator<Item = Point2>) -> Option<Extent> {
+        points.into_iter().fold(None, |extent, pos| {
+            let next = Point2::new(pos.x + 1, pos.y + 1);
+            Some(match extent {
+                None => Extent {
+                    min: pos,
+                    max: next,
+                },
+                Some(Extent { min, max }) => Extent {
+                    min: Point2::new(min.x.min(pos.x), min.y.min(pos.y)),
+                    max: Point2::new(max.x.max(next.x), max.y.max(next.y)),
+                },
+            })
+        })
+    }
+
+    pub fn of(frames: &[Frame]) -> Option<Extent> {
+        frames.iter().fold(None, |extent, frame| {
+            Some(extent.map_or_else(|| frame.into(), |extent| extent.union(frame)))
+        })
+    }
+
+    pub fn union(self, frame: &Frame) -> Extent {
+        let (min, max) = frame.extent();
+        Extent {
+            min: Point2::new(self.min.x.min(min.x), self.min.y.min(min.y)),
+            max: Point2::new(self.max.x.max(max.x), self.max.y.max(max.y)),
+        }
+    }
+}
+
+impl From<&Frame> for Extent {
+    fn from(frame: &Frame) -> Self {
+        let (min, max) = frame.extent();
+        Extent { min, max }
+    }
+}
+
+struct Sink {
+    every: usize,
+    seen: usize,
+    show: Box<dyn FnMut(Frame)>,
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+pub fn is_enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+// Only draws the frame when someone is watching and it isn't being skipped, since drawing
+// a whole grid per step would slow the solvers down
+pub fn frame(draw: impl FnOnce() -> Frame) {
+    SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            if sink.seen % sink.every == 0 {
+                (sink.show)(draw());
+            }
+            sink.seen += 1;
+        }
+    });
+}
+
+// Sends every `every`th frame drawn on this thread while `f` runs to `show`
+pub fn with_sink<T>(every: usize, show: impl FnMut(Frame) + 'static, f: impl FnOnce() -> T) -> T {
+    let sink = Sink {
+        every: every.max(1),
+        seen: 0,
+        show: Box::new(show),
+    };
+    let previous = SINK.with(|s| s.replace(Some(sink)));
+    let result = f();
+    SINK.with(|s| s.replace(previous));
+    result
+}
+
+pub fn record<T>(every: usize, f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    let frames = Rc::new(RefCell::new(vec![]));
+    let sink = frames.clone();
+    let result = with_sink(every, move |frame| sink.borrow_mut().push(frame), f);
+
+    (result, frames.take())
+}
+
+// Background, walls and rocks, sand, sources, light, blizzards, elves, mirrors and the rest
+const PALETTE: [[u8; 3]; 9] = [
+    [40, 40, 52],
+    [190, 190, 200],
+    [230, 190, 80],
+    [240, 90, 90],
+    [255, 230, 90],
+    [110, 170, 255],
+    [90, 220, 120],
+    [180, 120, 255],
+    [255, 255, 255],
+];
+
+fn palette_index(c: char) -> u8 {
+    match c {
+        '.' | ' ' => 0,
+        '#' => 1,
+        'o' | 'O' => 2,
+        '+' => 3,
+        '*' => 4,
+        '<' | '>' | '^' | 'v' | '2'..='9' => 5,
+        'E' | '@' => 6,
+        '|' | '-' | '/' | '\\' => 7,
+        _ => 8,
+    }
+}
+
+pub fn color(c: char) -> [u8; 3] {
+    PALETTE[palette_index(c) as usize]
+}
+
+// The frame in 24-bit terminal colours, only switching colour where it changes
+pub fn ansi(frame: &Frame) -> String {
+    let mut out = String::new();
+
+    for row in frame.cells.rows() {
+        let mut current = None;
+        for &c in row {
+            let [r, g, b] = color(c);
+            if current != Some((r, g, b)) {
+                write!(out, "\x1b[38;2;{r};{g};{b}m").unwrap();
+                current = Some((r, g, b));
+            }
+            out.push(c);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+
+    out
+}
+
+// Redraws the terminal with the frame, for animating a simulation in place
+pub fn write_ansi(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    write!(out, "\x1b[H\x1b[J{}", ansi(frame))?;
+    out.flush()
+}
+
+// Pixels per cell, so small grids don't end up as tiny images
+#[cfg(feature = "visualize")]
+fn scale(extent: Extent) -> usize {
+    let size = (extent.max - extent.min).x.max((extent.max - extent.min).y);
+    (800 / size.max(1) as usize).clamp(1, 8)
+}
+
+// Palette indices for `frame` placed on a canvas covering `extent`, `scale` pixels per cell
+#[cfg(feature = "visualize")]
+fn pixels(frame: &Frame, extent: Extent, scale: usize) -> (usize, usize, Vec<u8>) {
+    let size = extent.max - extent.min;
+    let (width, height) = (size.x as usize * scale, size.y as usize * scale);
+    let mut pixels = vec![0; width * height];
+
+    for ((x, y), &c) in frame.cells.iter() {
+        let Point2 { x, y } = frame.origin + Point2::new(x as i64, y as i64) - extent.min;
+        if x < 0 || y < 0 || x >= size.x || y >= size.y {
+            continue;
+        }
+
+        let (x, y) = (x as usize, y as usize);
+        for dy in 0..scale {
+            let row = (y * scale + dy) * width;
+            pixels[row + x * scale..row + (x + 1) * scale].fill(palette_index(c));
+        }
+    }
+
+    (width, height, pixels)
+}
+
+#[cfg(feature = "visualize")]
+pub fn write_png(out: impl Write, frame: &Frame) -> io::Result<()> {
+    let extent = Extent::from(frame);
+    let (width, height, pixels) = pixels(frame, extent, scale(extent));
+
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let rgb = pixels
+        .iter()
+        .flat_map(|&i| PALETTE[i as usize])
+        .collect::<Vec<_>>();
+    encoder.write_header()?.write_image_data(&rgb)?;
+    Ok(())
+}
+
+// An animated GIF on a canvas covering `extent`, which has to be known up front
+#[cfg(feature = "visualize")]
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    extent: Extent,
+    scale: usize,
+    delay: u16,
+}
+
+#[cfg(feature = "visualize")]
+impl<W: Write> GifWriter<W> {
+    pub fn new(out: W, extent: Extent, fps: u16) -> io::Result<Self> {
+        let scale = scale(extent);
+        let size = extent.max - extent.min;
+        let dimension = |cells: i64| {
+            u16::try_from(cells as usize * scale)
+                .map_err(|_| io::Error::other("the frames are too large for a GIF"))
+        };
+
+        let mut encoder = gif::Encoder::new(
+            out,
+            dimension(size.x)?,
+            dimension(size.y)?,
+            PALETTE.as_flattened(),
+        )
+        .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        Ok(GifWriter {
+            encoder,
+            extent,
+            scale,
+            delay: 100 / fps.max(1),
+        })
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height, pixels) = pixels(frame, self.extent, self.scale);
+        let frame = gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            buffer: pixels.into(),
+            delay: self.delay,
+            ..gif::Frame::default()
+        };
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+}
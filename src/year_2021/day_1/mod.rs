@@ -1,9 +1,14 @@
+use crate::{
+    error::{parse_number, SolveError},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -11,23 +16,42 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 7);
+            assert_eq!(result, Ok(7));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 1301);
+            let input = test_input(2021, 1);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(1301));
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+fn parse_depths(input: &str) -> Result<Vec<i32>, SolveError> {
     input
         .lines()
-        .map(|line| line.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>()
+        .map(|line| parse_number::<i32>(input, line))
+        .collect()
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    Ok(parse_depths(input)?
         .windows(2)
         .filter(|window| window[1] > window[0])
-        .count() as i32
+        .count() as i32)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_depths(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
 }
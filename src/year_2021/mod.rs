@@ -1 +1,5 @@
 pub mod day_1;
+
+use crate::solution::Registry;
+
+pub const DAYS: Registry = &[(1, &day_1::Solver)];
@@ -1,9 +1,15 @@
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::{blocks, int, lines},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -11,13 +17,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 24000);
+            assert_eq!(result, Ok(24000));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 70698);
+            let input = test_input(2022, 1);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(70698));
         }
     }
 
@@ -27,43 +36,53 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 45000);
+            assert_eq!(result, Ok(45000));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 206643);
+            let input = test_input(2022, 1);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(206643));
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> i32 {
-    input
-        .split("\n\n")
-        .map(|group| {
-            group
-                .lines()
-                .map(|line| line.parse::<i32>().unwrap())
-                .sum::<i32>()
-        })
+fn parse_calories(input: &str) -> Result<Vec<i32>, SolveError> {
+    let elves = parse_all(input, blocks(lines(int::<i32>)))?;
+    Ok(elves.into_iter().map(|elf| elf.into_iter().sum()).collect())
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    parse_calories(input)?
+        .into_iter()
         .max()
-        .unwrap()
+        .ok_or_else(|| SolveError::no_solution("no elves in input"))
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    let mut calories = input
-        .split("\n\n")
-        .map(|group| {
-            group
-                .lines()
-                .map(|line| line.parse::<i32>().unwrap())
-                .sum::<i32>()
-        })
-        .collect::<Vec<i32>>();
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
+    let mut calories = parse_calories(input)?;
 
     calories.sort_by(|a, b| a.cmp(b).reverse());
     calories.truncate(3);
 
-    calories.iter().sum()
+    Ok(calories.iter().sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_calories(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
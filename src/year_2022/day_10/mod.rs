@@ -1,9 +1,16 @@
+use crate::{
+    error::{parse_number, SolveError},
+    grid::Grid,
+    ocr,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -11,13 +18,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 13140);
+            assert_eq!(result, Ok(13140));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 11220);
+            let input = test_input(2022, 10);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(11220));
         }
     }
 
@@ -25,31 +35,57 @@ mod tests {
         use super::*;
 
         #[test]
-        fn it_solves_example() {
+        fn it_reports_unreadable_letters() {
             let result = solve_part2(EXAMPLE);
+            assert_eq!(
+                result,
+                Err(SolveError::no_solution(format!(
+                    "unrecognised letters on the CRT:\n{}",
+                    render_part2(EXAMPLE).unwrap()
+                )))
+            );
+        }
+
+        #[test]
+        #[ignore]
+        fn it_solves_input() {
+            let input = test_input(2022, 10);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok("BZPAJELK".to_string()));
+        }
+
+        #[test]
+        fn it_renders_example() {
+            let result = render_part2(EXAMPLE);
 
             assert_eq!(
                 result,
-                "##..##..##..##..##..##..##..##..##..##..
+                Ok("##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######....."
+                    .to_string())
             );
         }
 
         #[test]
-        fn it_solves_input() {
-            let result = solve_part2(INPUT);
+        #[ignore]
+        fn it_renders_input() {
+            let input = test_input(2022, 10);
+
+            let result = render_part2(&input);
             assert_eq!(
                 result,
-                "###..####.###...##....##.####.#....#..#.
+                Ok("###..####.###...##....##.####.#....#..#.
 #..#....#.#..#.#..#....#.#....#....#.#..
 ###....#..#..#.#..#....#.###..#....##...
 #..#..#...###..####....#.#....#....#.#..
 #..#.#....#....#..#.#..#.#....#....#.#..
 ###..####.#....#..#..##..####.####.#..#."
+                    .to_string())
             );
         }
     }
@@ -70,20 +106,25 @@ impl Command {
     }
 }
 
-fn parse_commands(input: &str) -> Vec<Command> {
+fn parse_commands(input: &str) -> Result<Vec<Command>, SolveError> {
     input
         .lines()
         .map(|line| match line.split_once(' ') {
-            Some(("addx", n)) => Command::AddX(n.parse().unwrap()),
-            _ => Command::Noop,
+            Some(("addx", n)) => Ok(Command::AddX(parse_number(input, n)?)),
+            None if line == "noop" => Ok(Command::Noop),
+            _ => Err(SolveError::parse(input, line, "unknown instruction")),
         })
         .collect()
 }
 
 const INTERESTING_CYCLES: [usize; 6] = [20, 60, 100, 140, 180, 220];
 
-pub fn solve_part1(input: &str) -> i32 {
-    let commands = parse_commands(input);
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    let commands = parse_commands(input)?;
+    if commands.is_empty() {
+        return Err(SolveError::no_solution("no instructions"));
+    }
+
     let mut cycle = 0;
     let mut x: i32 = 1;
     let mut sum: i32 = 0;
@@ -105,23 +146,28 @@ pub fn solve_part1(input: &str) -> i32 {
         }
     }
 
-    sum
+    Ok(sum)
 }
 
 const CRT_WIDTH: usize = 40;
 const CRT_HEIGHT: usize = 6;
 
-pub fn solve_part2(input: &str) -> String {
-    let commands = parse_commands(input);
+fn draw_crt(input: &str) -> Result<Grid<bool>, SolveError> {
+    let commands = parse_commands(input)?;
     let mut cycle = 0;
     let mut x: i32 = 1;
-    let mut crt = [[false; CRT_WIDTH]; CRT_HEIGHT];
+    let mut crt = Grid::filled(CRT_WIDTH, CRT_HEIGHT, false);
 
     for command in &commands {
         for _ in 0..command.cycles() {
             let row = cycle / CRT_WIDTH;
             let col = cycle % CRT_WIDTH;
-            crt[row][col] = col as i32 - x >= -1 && col as i32 - x <= 1;
+            if row >= CRT_HEIGHT {
+                return Err(SolveError::no_solution(
+                    "program runs past the last CRT row",
+                ));
+            }
+            crt[(col, row)] = col as i32 - x >= -1 && col as i32 - x <= 1;
             cycle += 1;
         }
 
@@ -131,12 +177,37 @@ pub fn solve_part2(input: &str) -> String {
         };
     }
 
-    crt.iter()
-        .map(|row| {
-            row.iter()
-                .map(|&b| if b { '#' } else { '.' })
-                .collect::<String>()
-        })
-        .collect::<Vec<String>>()
-        .join("\n")
+    Ok(crt)
+}
+
+// The CRT as drawn, for when the letters can't be read
+pub fn render_part2(input: &str) -> Result<String, SolveError> {
+    draw_crt(input).map(|crt| ocr::render(&crt))
+}
+
+pub fn solve_part2(input: &str) -> Result<String, SolveError> {
+    let crt = draw_crt(input)?;
+
+    ocr::decode(&crt).ok_or_else(|| {
+        SolveError::no_solution(format!(
+            "unrecognised letters on the CRT:\n{}",
+            ocr::render(&crt)
+        ))
+    })
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_commands(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -2,18 +2,26 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{multispace1, u64},
+    combinator::cut,
+    error::{Error, ErrorKind},
     multi::separated_list1,
     sequence::{delimited, preceded},
-    IResult, Parser,
+    Err, IResult, Parser,
 };
 use std::collections::VecDeque;
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::blocks,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     #[test]
     fn it_parses() {
@@ -53,19 +61,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE.replace("old + 6", "old - 6");
+        assert_eq!(
+            solve_part1(&input),
+            Err(SolveError::Parse {
+                line: 10,
+                column: 24,
+                message: "unexpected token".to_string()
+            })
+        );
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 10605);
+            assert_eq!(result, Ok(10605));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 117624);
+            let input = test_input(2022, 11);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(117624));
         }
     }
 
@@ -76,13 +100,16 @@ mod tests {
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
 
-            assert_eq!(result, 2713310158);
+            assert_eq!(result, Ok(2713310158));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 16792940265);
+            let input = test_input(2022, 11);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(16792940265));
         }
     }
 }
@@ -120,12 +147,16 @@ fn monkey_operation(input: &str) -> IResult<&str, MonkeyOperation> {
         ("*", MonkeyOperationValue::Old) => Ok((input, MonkeyOperation::Square)),
         ("*", MonkeyOperationValue::Value(value)) => Ok((input, MonkeyOperation::Multiply(value))),
         ("+", MonkeyOperationValue::Value(value)) => Ok((input, MonkeyOperation::Add(value))),
-        _ => unreachable!(),
+        _ => Err(Err::Failure(Error::new(input, ErrorKind::Verify))),
     }
 }
 
 fn monkey(input: &str) -> IResult<&str, Monkey> {
     let (input, _) = delimited(tag("Monkey "), u64, tag(":"))(input)?;
+    cut(monkey_body)(input)
+}
+
+fn monkey_body(input: &str) -> IResult<&str, Monkey> {
     let (input, _) = multispace1(input)?;
 
     let (input, items) = delimited(
@@ -154,11 +185,31 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
 }
 
 fn monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
-    separated_list1(tag("\n\n"), monkey)(input)
+    blocks(monkey)(input)
+}
+
+fn parse(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let monkeys = parse_all(input, monkeys)?;
+
+    for monkey in &monkeys {
+        for target in [monkey.monkey_if_true, monkey.monkey_if_false] {
+            if target >= monkeys.len() {
+                return Err(SolveError::no_solution(format!("no monkey {target}")));
+            }
+        }
+        if monkey.test_divisible_by == 0 {
+            return Err(SolveError::no_solution("divisibility test by zero"));
+        }
+    }
+    if monkeys.len() < 2 {
+        return Err(SolveError::no_solution("need at least two monkeys"));
+    }
+
+    Ok(monkeys)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, mut monkeys) = monkeys(input).unwrap();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let mut monkeys = parse(input)?;
     let mut monkey_inspections = vec![0; monkeys.len()];
     const ROUNDS: usize = 20;
 
@@ -196,11 +247,11 @@ pub fn solve_part1(input: &str) -> u32 {
     }
 
     monkey_inspections.sort_by(|a, b| b.cmp(a));
-    monkey_inspections[0] as u32 * monkey_inspections[1] as u32
+    Ok(monkey_inspections[0] as u32 * monkey_inspections[1] as u32)
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    let (_, mut monkeys) = monkeys(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let mut monkeys = parse(input)?;
     let mut monkey_inspections = vec![0; monkeys.len()];
     const ROUNDS: usize = 10_000;
 
@@ -243,5 +294,21 @@ pub fn solve_part2(input: &str) -> u64 {
     }
 
     monkey_inspections.sort_by(|a, b| b.cmp(a));
-    monkey_inspections[0] as u64 * monkey_inspections[1] as u64
+    Ok(monkey_inspections[0] as u64 * monkey_inspections[1] as u64)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
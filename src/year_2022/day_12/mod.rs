@@ -1,11 +1,16 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    search::bfs,
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -13,13 +18,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 31);
+            assert_eq!(result, Ok(31));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 352);
+            let input = test_input(2022, 12);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(352));
         }
     }
 
@@ -29,147 +37,91 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 29);
+            assert_eq!(result, Ok(29));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 345);
+            let input = test_input(2022, 12);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(345));
         }
     }
 }
 
-type Map = Vec<Vec<char>>;
+type Map = Grid<char>;
 type Position = (usize, usize);
 
-fn parse_map(input: &str) -> (Map, Position, Position) {
-    let mut start = None;
-    let mut end = None;
-    let map = input
-        .lines()
-        .enumerate()
-        .map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(col, c)| {
-                    if c == 'S' {
-                        start = Some((row, col));
-                        'a'
-                    } else if c == 'E' {
-                        end = Some((row, col));
-                        'z'
-                    } else {
-                        c
-                    }
-                })
-                .collect::<Vec<char>>()
-        })
-        .collect::<Map>();
-
-    (map, start.unwrap(), end.unwrap())
-}
+fn parse_map(input: &str) -> Result<(Map, Position, Position), SolveError> {
+    let mut map = Grid::parse(input, |c| matches!(c, 'S' | 'E' | 'a'..='z').then_some(c))?;
 
-fn get_neighbors(map: &Map, (row, col): Position, flip: bool) -> Vec<Position> {
-    let mut neighbors = Vec::new();
+    let start = map
+        .position(|&c| c == 'S')
+        .ok_or_else(|| SolveError::no_solution("missing start position"))?;
+    let end = map
+        .position(|&c| c == 'E')
+        .ok_or_else(|| SolveError::no_solution("missing best signal position"))?;
 
-    let item = map[row][col];
-    let mut neighbor_positions = vec![];
+    map[start] = 'a';
+    map[end] = 'z';
 
-    if row > 0 {
-        neighbor_positions.push((row - 1, col));
-    }
-    if row < map.len() - 1 {
-        neighbor_positions.push((row + 1, col));
-    }
-    if col > 0 {
-        neighbor_positions.push((row, col - 1));
-    }
-    if col < map[row].len() - 1 {
-        neighbor_positions.push((row, col + 1));
-    }
-
-    for (row, col) in &neighbor_positions {
-        let neighbor = map[*row][*col];
-        let diff = if flip {
-            item as i32 - neighbor as i32
-        } else {
-            neighbor as i32 - item as i32
-        };
-        if diff <= 1 {
-            neighbors.push((*row, *col));
-        }
-    }
+    Ok((map, start, end))
+}
 
-    neighbors
+fn get_neighbors(map: &Map, pos: Position, flip: bool) -> Vec<Position> {
+    let item = map[pos];
+
+    map.neighbors4(pos)
+        .filter(|&neighbor| {
+            let neighbor = map[neighbor];
+            let diff = if flip {
+                item as i32 - neighbor as i32
+            } else {
+                neighbor as i32 - item as i32
+            };
+            diff <= 1
+        })
+        .collect()
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (map, start, end) = parse_map(input);
-    let mut visited: HashSet<Position> = HashSet::new();
-    let mut q = VecDeque::new();
-    let mut prev: HashMap<Position, Position> = HashMap::new();
-
-    visited.insert(start);
-    q.push_back(start);
-
-    while let Some(pos) = q.pop_front() {
-        if pos == end {
-            let mut step = 0;
-            let mut curr = &end;
-
-            while let Some(pos) = prev.get(curr) {
-                step += 1;
-                curr = pos;
-            }
-            return step;
-        }
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let (map, start, end) = parse_map(input)?;
 
-        let neighbors = get_neighbors(&map, pos, false);
+    bfs(
+        start,
+        |&pos| get_neighbors(&map, pos, false),
+        |&pos| pos == end,
+    )
+    .map(|path| path.cost as u32)
+    .ok_or_else(|| SolveError::no_solution("no path to the best signal"))
+}
 
-        for neighbor in neighbors {
-            if !visited.contains(&neighbor) {
-                visited.insert(neighbor);
-                prev.insert(neighbor, pos);
-                q.push_back(neighbor);
-            }
-        }
-    }
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let (map, _, start) = parse_map(input)?;
 
-    unreachable!();
+    bfs(
+        start,
+        |&pos| get_neighbors(&map, pos, true),
+        |&pos| map[pos] == 'a',
+    )
+    .map(|path| path.cost as u32)
+    .ok_or_else(|| SolveError::no_solution("no path from any lowest square"))
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (map, _, start) = parse_map(input);
-    let mut visited: HashSet<Position> = HashSet::new();
-    let mut q = VecDeque::new();
-    let mut prev: HashMap<Position, Position> = HashMap::new();
-
-    visited.insert(start);
-    q.push_back(start);
-
-    while let Some(pos) = q.pop_front() {
-        if map[pos.0][pos.1] == 'a' {
-            let mut step = 0;
-            let mut curr = &pos;
-
-            while let Some(pos) = prev.get(curr) {
-                step += 1;
-                curr = pos;
-            }
-            return step;
-        }
+pub struct Solver;
 
-        let neighbors = get_neighbors(&map, pos, true);
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_map(input).map(|_| ()))
+    }
 
-        for neighbor in neighbors {
-            if !visited.contains(&neighbor) {
-                visited.insert(neighbor);
-                prev.insert(neighbor, pos);
-                q.push_back(neighbor);
-            }
-        }
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
     }
 
-    unreachable!();
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,24 +1,70 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{newline, u32},
+    character::complete::{line_ending, u32},
     combinator::map,
-    multi::{separated_list0, separated_list1},
+    multi::separated_list0,
     sequence::{delimited, separated_pair},
     IResult,
 };
 use std::cmp::Ordering;
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::blocks,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
+    use itertools::Itertools;
+    use proptest::prelude::*;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    fn packet() -> impl Strategy<Value = Item> {
+        let single = (0..12u32).prop_map(Item::SingleItem);
+        single.prop_recursive(4, 32, 5, |item| {
+            prop::collection::vec(item, 0..5).prop_map(Item::ArrayItem)
+        })
+    }
+
+    fn render(item: &Item) -> String {
+        match item {
+            Item::SingleItem(n) => n.to_string(),
+            Item::ArrayItem(items) => format!("[{}]", items.iter().map(render).join(",")),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn it_parses_rendered_packets(pairs in prop::collection::vec((packet(), packet()), 1..10)) {
+            let input = pairs
+                .iter()
+                .map(|(a, b)| format!("{}\n{}", render(a), render(b)))
+                .join("\n\n");
+            prop_assert_eq!(parse_pairs(&input), Ok(pairs));
+        }
+
+        #[test]
+        fn it_orders_packets_totally(a in packet(), b in packet(), c in packet()) {
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            if a.packet_cmp(&b).is_ne() {
+                prop_assert_eq!(a.cmp(&b), a.packet_cmp(&b));
+            }
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+        }
+    }
 
     #[test]
     fn it_parses() {
-        let pairs = parse_pairs(EXAMPLE);
+        let pairs = parse_pairs(EXAMPLE).unwrap();
         assert_eq!(
             pairs,
             vec![
@@ -140,7 +186,7 @@ mod tests {
 
     #[test]
     fn it_compares() {
-        let pairs = parse_pairs(EXAMPLE);
+        let pairs = parse_pairs(EXAMPLE).unwrap();
         let comparisons = pairs.iter().map(|(a, b)| a <= b).collect::<Vec<_>>();
         assert_eq!(
             comparisons,
@@ -154,13 +200,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 13);
+            assert_eq!(result, Ok(13));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 5808);
+            let input = test_input(2022, 13);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(5808));
         }
     }
 
@@ -170,13 +219,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 140);
+            assert_eq!(result, Ok(140));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 22713);
+            let input = test_input(2022, 13);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(22713));
         }
     }
 }
@@ -189,21 +241,39 @@ enum Item {
     SingleItem(u32),
 }
 
-impl Ord for Item {
-    fn cmp(&self, other: &Self) -> Ordering {
+impl Item {
+    // The puzzle's order, which treats `1` and `[1]` as equal
+    fn packet_cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
             (Item::SingleItem(a), Item::SingleItem(b)) => a.cmp(b),
-            (Item::ArrayItem(a), Item::ArrayItem(b)) => a.cmp(b),
+            (Item::ArrayItem(a), Item::ArrayItem(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.packet_cmp(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
             (Item::SingleItem(a), Item::ArrayItem(_)) => {
-                Item::ArrayItem(vec![Item::SingleItem(*a)]).cmp(other)
+                Item::ArrayItem(vec![Item::SingleItem(*a)]).packet_cmp(other)
             }
             (Item::ArrayItem(_), Item::SingleItem(b)) => {
-                self.cmp(&Item::ArrayItem(vec![Item::SingleItem(*b)]))
+                self.packet_cmp(&Item::ArrayItem(vec![Item::SingleItem(*b)]))
             }
         }
     }
 }
 
+// Ties in the puzzle's order are broken by structure, so `Ord` agrees with `Eq`
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.packet_cmp(other).then_with(|| match (self, other) {
+            (Item::SingleItem(a), Item::SingleItem(b)) => a.cmp(b),
+            (Item::ArrayItem(a), Item::ArrayItem(b)) => a.cmp(b),
+            (Item::SingleItem(_), Item::ArrayItem(_)) => Ordering::Less,
+            (Item::ArrayItem(_), Item::SingleItem(_)) => Ordering::Greater,
+        })
+    }
+}
+
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -228,28 +298,26 @@ fn item(input: &str) -> IResult<&str, Item> {
 }
 
 fn item_pair(input: &str) -> IResult<&str, ItemPair> {
-    separated_pair(item, newline, item)(input)
+    separated_pair(item, line_ending, item)(input)
 }
 
-fn parse_pairs(input: &str) -> Vec<ItemPair> {
-    let (_, pairs) = separated_list1(tag("\n\n"), item_pair)(input).unwrap();
-
-    pairs
+fn parse_pairs(input: &str) -> Result<Vec<ItemPair>, SolveError> {
+    parse_all(input, blocks(item_pair))
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let pairs = parse_pairs(input);
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let pairs = parse_pairs(input)?;
 
-    pairs
+    Ok(pairs
         .iter()
         .enumerate()
-        .filter_map(|(i, (a, b))| if a <= b { Some(i as u32 + 1) } else { None })
-        .sum()
+        .filter_map(|(i, (a, b))| a.packet_cmp(b).is_le().then_some(i as u32 + 1))
+        .sum())
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let pairs = parse_pairs(input);
-    let divider_packets = parse_pairs("[[2]]\n[[6]]");
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let pairs = parse_pairs(input)?;
+    let divider_packets = parse_pairs("[[2]]\n[[6]]")?;
 
     let mut items = pairs
         .iter()
@@ -260,7 +328,7 @@ pub fn solve_part2(input: &str) -> u32 {
     items.sort();
 
     let (first, second) = &divider_packets[0];
-    items
+    Ok(items
         .iter()
         .enumerate()
         .filter_map(|(i, item)| {
@@ -270,5 +338,21 @@ pub fn solve_part2(input: &str) -> u32 {
                 None
             }
         })
-        .product()
+        .product())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_pairs(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
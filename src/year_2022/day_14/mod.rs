@@ -1,18 +1,28 @@
 use nom::{
     bytes::complete::tag,
-    character::complete::{i32, newline},
+    character::complete::i64,
+    combinator::{map, verify},
     multi::separated_list1,
     sequence::separated_pair,
     IResult,
 };
-use std::{cmp::Ordering, collections::HashMap};
+use std::collections::HashMap;
+
+use crate::{
+    error::{parse_all, SolveError},
+    geometry::Point2,
+    parsers::lines,
+    solution::{Answer, Solution},
+    trace_event,
+    visualize::{self, Frame},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -20,13 +30,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 24);
+            assert_eq!(result, Ok(24));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 719);
+            let input = test_input(2022, 14);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(719));
         }
     }
 
@@ -36,32 +49,40 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 93);
+            assert_eq!(result, Ok(93));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 23390);
+            let input = test_input(2022, 14);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(23390));
         }
     }
 }
 
-type Position = (i32, i32);
-
-fn position(input: &str) -> IResult<&str, Position> {
-    separated_pair(i32, tag(","), i32)(input)
+fn position(input: &str) -> IResult<&str, Point2> {
+    map(separated_pair(i64, tag(","), i64), Point2::from)(input)
 }
 
-fn position_sequence(input: &str) -> IResult<&str, Vec<Position>> {
-    separated_list1(tag(" -> "), position)(input)
+fn position_sequence(input: &str) -> IResult<&str, Vec<Point2>> {
+    verify(
+        separated_list1(tag(" -> "), position),
+        |sequence: &[Point2]| {
+            sequence
+                .windows(2)
+                .all(|window| window[0].x == window[1].x || window[0].y == window[1].y)
+        },
+    )(input)
 }
 
-const SAND_SOURCE: Position = (500, 0);
+const SAND_SOURCE: Point2 = Point2::new(500, 0);
 
 struct Map {
-    map: HashMap<Position, MapCell>,
-    bounds: (Position, Position),
+    map: HashMap<Point2, MapCell>,
+    bounds: (Point2, Point2),
     floor: bool,
 }
 
@@ -73,8 +94,8 @@ enum MapCell {
 }
 
 impl Map {
-    fn parse(input: &str, floor: bool) -> Map {
-        let (_, position_sequences) = separated_list1(newline, position_sequence)(input).unwrap();
+    fn parse(input: &str, floor: bool) -> Result<Map, SolveError> {
+        let position_sequences = parse_all(input, lines(position_sequence))?;
 
         let mut map = HashMap::new();
         let mut bounds = (SAND_SOURCE, SAND_SOURCE);
@@ -83,32 +104,36 @@ impl Map {
             for window in row.windows(2) {
                 let (from, to) = (window[0], window[1]);
 
-                let (to_x, to_y) = to;
-
                 let mut curr = from;
 
                 while curr != to {
                     map.insert(curr, MapCell::Rock);
 
-                    let (curr_x, curr_y) = &mut curr;
-
-                    bounds.0 = (bounds.0 .0.min(*curr_x), bounds.0 .1.min(*curr_y));
-                    bounds.1 = (bounds.1 .0.max(*curr_x), bounds.1 .1.max(*curr_y));
+                    bounds.0 = Point2::new(bounds.0.x.min(curr.x), bounds.0.y.min(curr.y));
+                    bounds.1 = Point2::new(bounds.1.x.max(curr.x), bounds.1.y.max(curr.y));
 
-                    match (to_x.cmp(curr_x), to_y.cmp(curr_y)) {
-                        (Ordering::Less, Ordering::Equal) => *curr_x -= 1,
-                        (Ordering::Greater, Ordering::Equal) => *curr_x += 1,
-                        (Ordering::Equal, Ordering::Less) => *curr_y -= 1,
-                        (Ordering::Equal, Ordering::Greater) => *curr_y += 1,
-                        _ => panic!("We only support vertical / horizontal lines"),
-                    }
+                    // Rock paths are verified to be straight, so this walks along a single axis
+                    curr += (to - curr).signum();
                 }
 
                 map.insert(to, MapCell::Rock);
             }
         }
 
-        Map { map, bounds, floor }
+        Ok(Map { map, bounds, floor })
+    }
+
+    fn draw(&self) -> Frame {
+        let cells = self.map.iter().map(|(&pos, cell)| {
+            let c = match cell {
+                MapCell::Rock => '#',
+                MapCell::Sand => 'o',
+                MapCell::Air => '.',
+            };
+            (pos, c)
+        });
+
+        Frame::sparse(cells.chain([(SAND_SOURCE, '+')]), '.')
     }
 
     fn drop_sand(self: &mut Map) -> DropSandResult {
@@ -119,16 +144,16 @@ impl Map {
         while changed {
             changed = false;
             let drop_options = [
-                (sand_pos.0, sand_pos.1 + 1),
-                (sand_pos.0 - 1, sand_pos.1 + 1),
-                (sand_pos.0 + 1, sand_pos.1 + 1),
+                sand_pos + Point2::new(0, 1),
+                sand_pos + Point2::new(-1, 1),
+                sand_pos + Point2::new(1, 1),
             ];
 
             for option in drop_options.iter() {
                 let target = *option;
 
                 let dest_cell = self.map.get(&target).unwrap_or(
-                    if self.floor && target.1 >= self.bounds.1 .1 + 2 {
+                    if self.floor && target.y >= self.bounds.1.y + 2 {
                         &MapCell::Rock
                     } else {
                         &MapCell::Air
@@ -149,16 +174,18 @@ impl Map {
 
             // If sand_pos is out of self.bounds, we fell into the void
             if !self.floor
-                && (sand_pos.0 < self.bounds.0 .0
-                    || sand_pos.0 > self.bounds.1 .0
-                    || sand_pos.1 < self.bounds.0 .1
-                    || sand_pos.1 > self.bounds.1 .1)
+                && (sand_pos.x < self.bounds.0.x
+                    || sand_pos.x > self.bounds.1.x
+                    || sand_pos.y < self.bounds.0.y
+                    || sand_pos.y > self.bounds.1.y)
             {
                 return DropSandResult::FellIntoEndlessVoid;
             }
         }
 
+        trace_event!("sand settled", x = sand_pos.x, y = sand_pos.y);
         self.map.insert(sand_pos, MapCell::Sand);
+        visualize::frame(|| self.draw());
 
         DropSandResult::CameToRest
     }
@@ -171,8 +198,8 @@ enum DropSandResult {
     BlockedSource,
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let mut map = Map::parse(input, false);
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let mut map = Map::parse(input, false)?;
 
     let mut count = 0;
 
@@ -180,11 +207,11 @@ pub fn solve_part1(input: &str) -> u32 {
         count += 1;
     }
 
-    count
+    Ok(count)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let mut map = Map::parse(input, true);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let mut map = Map::parse(input, true)?;
 
     let mut count = 1;
 
@@ -192,5 +219,21 @@ pub fn solve_part2(input: &str) -> u32 {
         count += 1;
     }
 
-    count
+    Ok(count)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(Map::parse(input, false).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,18 +1,33 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::{i32, line_ending},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult,
-};
+use nom::{bytes::complete::tag, character::complete::i64, sequence::preceded, IResult};
 use std::collections::HashSet;
 
+use crate::{
+    error::{parse_all, SolveError},
+    geometry::Point2,
+    parsers::lines,
+    ranges::RangeSet,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE.replace("Sensor at x=9", "Sensor at x9");
+        assert_eq!(
+            solve_part1(&input, 10),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 1,
+                message: "unexpected input".to_string()
+            })
+        );
+    }
 
     mod part1 {
         use super::*;
@@ -20,13 +35,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE, 10);
-            assert_eq!(result, 26);
+            assert_eq!(result, Ok(26));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT, 2_000_000);
-            assert_eq!(result, 5_508_234);
+            let input = test_input(2022, 15);
+
+            let result = solve_part1(&input, 2_000_000);
+            assert_eq!(result, Ok(5_508_234));
         }
     }
 
@@ -36,135 +54,127 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE, 20);
-            assert_eq!(result, 56_000_011);
+            assert_eq!(result, Ok(56_000_011));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT, 4_000_000);
-            assert_eq!(result, 10_457_634_860_779);
+            let input = test_input(2022, 15);
+
+            let result = solve_part2(&input, 4_000_000);
+            assert_eq!(result, Ok(10_457_634_860_779));
         }
     }
 }
 
-type Position = (i32, i32);
-type Reading = (Position, Position);
+type Reading = (Point2, Point2);
 
-fn position(input: &str) -> IResult<&str, Position> {
+fn position(input: &str) -> IResult<&str, Point2> {
     let (input, _) = tag("x=")(input)?;
-    let (input, x) = i32(input)?;
+    let (input, x) = i64(input)?;
     let (input, _) = tag(", y=")(input)?;
-    let (input, y) = i32(input)?;
+    let (input, y) = i64(input)?;
 
-    Ok((input, (x, y)))
+    Ok((input, Point2::new(x, y)))
 }
-fn beacon(input: &str) -> IResult<&str, Position> {
+fn beacon(input: &str) -> IResult<&str, Point2> {
     // "closest beacon is at x=-2, y=15"
     preceded(tag("closest beacon is at "), position)(input)
 }
-fn sensor(input: &str) -> IResult<&str, Position> {
+fn sensor(input: &str) -> IResult<&str, Point2> {
     // "Sensor at x=2, y=18: "
     preceded(tag("Sensor at "), position)(input)
 }
 fn reading(input: &str) -> IResult<&str, Reading> {
     let (input, sensor) = sensor(input)?;
     let (input, _) = tag(": ")(input)?;
-    let (input, beacon) = beacon(&input)?;
+    let (input, beacon) = beacon(input)?;
 
     Ok((input, (sensor, beacon)))
 }
-fn parse_readings(input: &str) -> Vec<Reading> {
-    separated_list1(line_ending, reading)(input).unwrap().1
-}
-
-fn manhattan_distance(a: &Position, b: &Position) -> u32 {
-    ((a.0 - b.0).abs() + (a.1 - b.1).abs()) as u32
+fn parse_readings(input: &str) -> Result<Vec<Reading>, SolveError> {
+    parse_all(input, lines(reading))
 }
 
-pub fn solve_part1(input: &str, y: i32) -> u32 {
-    let sensors = parse_readings(input);
-    let beacons: HashSet<Position> = sensors.iter().map(|(_, beacon)| beacon).cloned().collect();
+pub fn solve_part1(input: &str, y: i64) -> Result<u32, SolveError> {
+    let sensors = parse_readings(input)?;
 
-    sensors
+    let covered: RangeSet<i64> = sensors
         .iter()
         .filter_map(|(sensor, beacon)| {
-            let distance = manhattan_distance(sensor, beacon);
+            let distance = sensor.manhattan(*beacon);
+            let y_distance = sensor.y.abs_diff(y);
+            let x_distance = distance.checked_sub(y_distance)? as i64;
 
-            let min_y = sensor.1 - distance as i32;
-            let max_y = sensor.1 + distance as i32;
-
-            if min_y <= y && y <= max_y {
-                Some((sensor, distance))
-            } else {
-                None
-            }
+            Some(sensor.x - x_distance..sensor.x + x_distance + 1)
         })
-        .flat_map(|(sensor, distance)| {
-            let y_distance = (y - sensor.1).abs() as u32;
-            let x_distance = distance - y_distance;
+        .collect();
+    let beacons: RangeSet<i64> = sensors
+        .iter()
+        .filter(|(_, beacon)| beacon.y == y)
+        .map(|(_, beacon)| beacon.x..beacon.x + 1)
+        .collect();
 
-            (sensor.0 - x_distance as i32..=sensor.0 + x_distance as i32).map(move |x| (x, y))
-        })
-        .filter(|pos| !beacons.contains(pos))
-        .collect::<HashSet<Position>>()
-        .len() as u32
+    Ok(covered.difference(&beacons).len() as u32)
 }
 
-pub fn solve_part2(input: &str, search_space: i32) -> i64 {
-    let sensors = parse_readings(input);
+pub fn solve_part2(input: &str, search_space: i64) -> Result<i64, SolveError> {
+    let sensors = parse_readings(input)?;
 
-    let sensor_distances: Vec<(Position, u32)> = sensors
+    let sensor_distances: Vec<(Point2, u64)> = sensors
         .iter()
-        .map(|(sensor, beacon)| (sensor.clone(), manhattan_distance(sensor, beacon)))
+        .map(|(sensor, beacon)| (*sensor, sensor.manhattan(*beacon)))
         .collect();
 
-    let search_bounds = ((0, 0), (search_space, search_space));
+    let search_bounds = (Point2::ORIGIN, Point2::new(search_space, search_space));
 
-    let mut possibilities: HashSet<Position> = sensor_distances
+    let mut possibilities: HashSet<Point2> = sensor_distances
         .iter()
-        .map(|(pos, distance)| get_perimeter(pos, *distance as i32, &search_bounds))
-        .flatten()
+        .flat_map(|(pos, distance)| get_perimeter(pos, *distance as i64, &search_bounds))
         .collect();
 
-    for ((x, y), distance) in &sensor_distances {
-        possibilities.retain(|pos| manhattan_distance(pos, &(*x, *y)) > *distance);
+    for (sensor, distance) in &sensor_distances {
+        possibilities.retain(|pos| pos.manhattan(*sensor) > *distance);
     }
 
     for (_, beacon) in &sensors {
         possibilities.remove(beacon);
     }
 
-    dbg!(&possibilities);
-
-    let (x, y) = possibilities.iter().next().unwrap();
+    let Some(Point2 { x, y }) = possibilities.iter().next() else {
+        return Err(SolveError::no_solution(
+            "every position in the search space is covered",
+        ));
+    };
 
-    *x as i64 * 4_000_000 as i64 + *y as i64
+    Ok(x * 4_000_000 + y)
 }
 
-fn get_perimeter(pos: &Position, distance: i32, bounds: &(Position, Position)) -> Vec<Position> {
-    let (x, y) = pos;
+fn get_perimeter(pos: &Point2, distance: i64, bounds: &(Point2, Point2)) -> Vec<Point2> {
+    let Point2 { x, y } = pos;
 
     let mut perimeter = Vec::new();
 
     let distance = distance + 1;
 
     for i in 0..distance {
-        let pos_on_top_left_diagonal = (x - distance + i, y - i);
+        let pos_on_top_left_diagonal = Point2::new(x - distance + i, y - i);
         if in_bounds(&pos_on_top_left_diagonal, bounds) {
             perimeter.push(pos_on_top_left_diagonal);
         }
 
-        let pos_on_top_right_diagonal = (x + i, y - distance + i);
+        let pos_on_top_right_diagonal = Point2::new(x + i, y - distance + i);
         if in_bounds(&pos_on_top_right_diagonal, bounds) {
             perimeter.push(pos_on_top_right_diagonal);
         }
 
-        let pos_on_bottom_right_diagonal = (x + distance - i, y + i);
+        let pos_on_bottom_right_diagonal = Point2::new(x + distance - i, y + i);
         if in_bounds(&pos_on_bottom_right_diagonal, bounds) {
             perimeter.push(pos_on_bottom_right_diagonal);
         }
 
-        let pos_on_bottom_left_diagonal = (x - i, y + distance - i);
+        let pos_on_bottom_left_diagonal = Point2::new(x - i, y + distance - i);
         if in_bounds(&pos_on_bottom_left_diagonal, bounds) {
             perimeter.push(pos_on_bottom_left_diagonal);
         }
@@ -173,6 +183,22 @@ fn get_perimeter(pos: &Position, distance: i32, bounds: &(Position, Position)) -
     perimeter
 }
 
-fn in_bounds(pos: &Position, bounds: &(Position, Position)) -> bool {
-    pos.0 >= bounds.0 .0 && pos.0 <= bounds.1 .0 && pos.1 >= bounds.0 .1 && pos.1 <= bounds.1 .1
+fn in_bounds(pos: &Point2, bounds: &(Point2, Point2)) -> bool {
+    pos.x >= bounds.0.x && pos.x <= bounds.1.x && pos.y >= bounds.0.y && pos.y <= bounds.1.y
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_readings(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input, 2_000_000).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input, 4_000_000).map(Answer::from))
+    }
 }
@@ -2,20 +2,60 @@ use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, newline, u32},
+    character::complete::{alpha1, u32},
     multi::separated_list1,
     sequence::{delimited, preceded},
     IResult,
 };
 use petgraph::{algo::floyd_warshall, prelude::DiGraphMap};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    dot::Dot,
+    error::{parse_all, SolveError},
+    memo::Memo,
+    parsers::lines,
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE.replace("Valve CC has flow rate=2", "Valve CC has flow rate 2");
+        assert_eq!(
+            solve_part1(&input),
+            Err(SolveError::Parse {
+                line: 3,
+                column: 1,
+                message: "unexpected input".to_string()
+            })
+        );
+        assert_eq!(
+            solve_part2(&EXAMPLE.replace("AA", "ZZ")),
+            Err(SolveError::NoSolution(
+                "there's no valve AA to start from".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn it_exports_dot() {
+        let dot = dot(EXAMPLE).unwrap().to_string();
+
+        assert!(dot.starts_with(
+            "graph valves {\n    \"AA\" [label=\"AA\\n0\", shape=\"doublecircle\"];\n"
+        ));
+        assert!(dot.contains("    \"BB\" [label=\"BB\\n13\", shape=\"box\"];\n"));
+        assert!(dot.contains("    \"FF\" [label=\"FF\\n0\", shape=\"ellipse\"];\n"));
+        assert!(dot.contains("    \"AA\" -- \"BB\";\n    \"AA\" -- \"DD\";\n"));
+        assert_eq!(dot.matches(" -- ").count(), 10);
+    }
 
     mod part1 {
         use super::*;
@@ -23,13 +63,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 1651);
+            assert_eq!(result, Ok(1651));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 1862);
+            let input = test_input(2022, 16);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(1862));
         }
     }
 
@@ -39,13 +82,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 1707);
+            assert_eq!(result, Ok(1707));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 2422);
+            let input = test_input(2022, 16);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(2422));
         }
     }
 }
@@ -63,11 +109,11 @@ fn report_entry(input: &str) -> IResult<&str, (&str, u32, Vec<&str>)> {
 
     Ok((input, (name, flow_rate, tunnels)))
 }
-fn reports(input: &str) -> IResult<&str, Vec<(&str, u32, Vec<&str>)>> {
-    separated_list1(newline, report_entry)(input)
-}
-fn parse_graph(input: &str) -> (DiGraphMap<&str, u32>, HashMap<&str, u32>) {
-    let (_, entries) = reports(input).unwrap();
+
+type Valves<'a> = (DiGraphMap<&'a str, u32>, HashMap<&'a str, u32>);
+
+fn parse_graph(input: &str) -> Result<Valves<'_>, SolveError> {
+    let entries = parse_all(input, lines(report_entry))?;
     let mut graph = DiGraphMap::new();
     let mut flow_rates = HashMap::new();
 
@@ -82,49 +128,92 @@ fn parse_graph(input: &str) -> (DiGraphMap<&str, u32>, HashMap<&str, u32>) {
         }
     }
 
-    (graph, flow_rates)
+    if !flow_rates.contains_key("AA") {
+        return Err(SolveError::no_solution("there's no valve AA to start from"));
+    }
+
+    Ok((graph, flow_rates))
+}
+
+// Steps between every pair of valves, leaving out the ones that can't reach each other
+fn distances<'a>(graph: &DiGraphMap<&'a str, u32>) -> HashMap<(&'a str, &'a str), u32> {
+    // Every tunnel takes a minute, so there are no negative cycles
+    let distances = floyd_warshall(graph, |_| 1).unwrap_or_default();
+
+    distances
+        .into_iter()
+        .filter(|(_, distance)| *distance != u32::MAX)
+        .collect()
+}
+
+// Valves worth opening are boxes labelled with their flow rate. Every tunnel goes both ways,
+// so it's drawn once.
+pub fn dot(input: &str) -> Result<Dot, SolveError> {
+    let (graph, flow_rates) = parse_graph(input)?;
+    let mut dot = Dot::graph("valves");
+
+    for valve in graph.nodes().sorted() {
+        let label = format!("{valve}\n{}", flow_rates[valve]);
+        let shape = match (valve, flow_rates[valve]) {
+            ("AA", _) => "doublecircle",
+            (_, 0) => "ellipse",
+            _ => "box",
+        };
+        dot.node(valve, &[("label", &label), ("shape", shape)]);
+    }
+    for (from, to, _) in graph.all_edges().filter(|(from, to, _)| from < to).sorted() {
+        dot.edge(from, to, &[]);
+    }
+
+    Ok(dot)
 }
 
-fn get_max_total_flow(
-    graph: &DiGraphMap<&str, u32>,
-    flow_rates: &HashMap<&str, u32>,
-    closed_valves: HashSet<&str>,
-    distances: &HashMap<(&str, &str), u32>,
-    curr: &str,
+type FlowMemo<'a> = Memo<(&'a str, u32, BTreeSet<&'a str>), u32>;
+
+fn get_max_total_flow<'a>(
+    flow_rates: &HashMap<&'a str, u32>,
+    closed_valves: BTreeSet<&'a str>,
+    distances: &HashMap<(&'a str, &'a str), u32>,
+    curr: &'a str,
     time_left: u32,
+    memo: &mut FlowMemo<'a>,
 ) -> u32 {
-    closed_valves
-        .iter()
-        .map(|valve| (valve, 1 + distances[&(curr, *valve)]))
-        .filter(|(_, distance)| *distance < time_left)
-        .map(|(target_valve, distance)| {
-            let mut new_closed_valves = closed_valves.clone();
-            new_closed_valves.remove(target_valve);
-
-            let flow_rate = flow_rates[target_valve];
-            let total_flow = (time_left - distance) * flow_rate;
-
-            get_max_total_flow(
-                graph,
-                flow_rates,
-                new_closed_valves,
-                distances,
-                target_valve,
-                time_left - distance,
-            ) + total_flow
-        })
-        .max()
-        .unwrap_or(0)
+    let key = (curr, time_left, closed_valves.clone());
+
+    memo.get_or_insert_with(key, |memo| {
+        closed_valves
+            .iter()
+            .filter_map(|valve| Some((*valve, 1 + distances.get(&(curr, *valve))?)))
+            .filter(|(_, distance)| *distance < time_left)
+            .map(|(target_valve, distance)| {
+                let mut new_closed_valves = closed_valves.clone();
+                new_closed_valves.remove(target_valve);
+
+                let flow_rate = flow_rates[target_valve];
+                let total_flow = (time_left - distance) * flow_rate;
+
+                get_max_total_flow(
+                    flow_rates,
+                    new_closed_valves,
+                    distances,
+                    target_valve,
+                    time_left - distance,
+                    memo,
+                ) + total_flow
+            })
+            .max()
+            .unwrap_or(0)
+    })
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (g, flow_rates) = parse_graph(input);
-    let distances = floyd_warshall(&g, |_| 1).unwrap();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let (g, flow_rates) = parse_graph(input)?;
+    let distances = distances(&g);
+    let mut memo = Memo::new();
 
-    get_max_total_flow(
-        &g,
+    let total_flow = get_max_total_flow(
         &flow_rates,
-        HashSet::from_iter(
+        BTreeSet::from_iter(
             flow_rates
                 .iter()
                 .filter(|(_, flow_rate)| **flow_rate > 0)
@@ -133,14 +222,18 @@ pub fn solve_part1(input: &str) -> u32 {
         &distances,
         "AA",
         30,
-    )
+        &mut memo,
+    );
+
+    memo.trace_stats("max total flow");
+    Ok(total_flow)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (g, flow_rates) = parse_graph(input);
-    let distances = floyd_warshall(&g, |_| 1).unwrap();
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let (g, flow_rates) = parse_graph(input)?;
+    let distances = distances(&g);
 
-    let closed_valves: HashSet<&str> = HashSet::from_iter(
+    let closed_valves: BTreeSet<&str> = BTreeSet::from_iter(
         flow_rates
             .iter()
             .filter(|(_, flow_rate)| **flow_rate > 0)
@@ -148,23 +241,54 @@ pub fn solve_part2(input: &str) -> u32 {
     );
 
     let closed_valves_clone = closed_valves.clone();
+    let mut memo = Memo::new();
 
-    closed_valves
+    let total_flow = closed_valves
         .into_iter()
         .powerset()
-        .map(|vec| vec.iter().copied().collect::<HashSet<_>>())
+        .map(|vec| vec.iter().copied().collect::<BTreeSet<_>>())
         .map(|human_set| {
             let elephant_valves = closed_valves_clone
                 .difference(&human_set)
                 .copied()
-                .collect::<HashSet<_>>();
+                .collect::<BTreeSet<_>>();
 
             (human_set, elephant_valves)
         })
         .map(|(human_valves, elephant_valves)| {
-            get_max_total_flow(&g, &flow_rates, human_valves, &distances, "AA", 26)
-                + get_max_total_flow(&g, &flow_rates, elephant_valves, &distances, "AA", 26)
+            get_max_total_flow(&flow_rates, human_valves, &distances, "AA", 26, &mut memo)
+                + get_max_total_flow(
+                    &flow_rates,
+                    elephant_valves,
+                    &distances,
+                    "AA",
+                    26,
+                    &mut memo,
+                )
         })
         .max()
-        .unwrap_or(0)
+        .unwrap_or(0);
+
+    memo.trace_stats("max total flow");
+    Ok(total_flow)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_graph(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+
+    fn dot(&self, input: &str) -> Option<Result<Dot, SolveError>> {
+        Some(dot(input))
+    }
 }
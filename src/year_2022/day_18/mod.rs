@@ -1,17 +1,18 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::{i32, newline},
-    multi::separated_list1,
-    IResult,
-};
+use nom::{bytes::complete::tag, character::complete::i32, IResult};
 use std::collections::{HashSet, VecDeque};
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -19,19 +20,22 @@ mod tests {
         #[test]
         fn it_solves_smaller_example() {
             let result = solve_part1("1,1,1\n2,1,1");
-            assert_eq!(result, 10);
+            assert_eq!(result, Ok(10));
         }
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 64);
+            assert_eq!(result, Ok(64));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 4370);
+            let input = test_input(2022, 18);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(4370));
         }
     }
 
@@ -41,13 +45,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 58);
+            assert_eq!(result, Ok(58));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 2458);
+            let input = test_input(2022, 18);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(2458));
         }
     }
 }
@@ -61,10 +68,8 @@ fn parse_line(input: &str) -> IResult<&str, Point> {
 
     Ok((input, (x, y, z)))
 }
-fn parse(input: &str) -> Vec<Point> {
-    let (_, coords) = separated_list1(newline, parse_line)(input).unwrap();
-
-    coords
+fn parse(input: &str) -> Result<Vec<Point>, SolveError> {
+    parse_all(input, lines(parse_line))
 }
 
 type Point = (i32, i32, i32);
@@ -80,8 +85,8 @@ fn cube_neighbors(&(x, y, z): &Point) -> [Point; 6] {
     ]
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let cubes = parse(input).into_iter().collect::<HashSet<Point>>();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let cubes = parse(input)?.into_iter().collect::<HashSet<Point>>();
 
     let mut surface_area = 0;
 
@@ -93,7 +98,7 @@ pub fn solve_part1(input: &str) -> u32 {
         }
     }
 
-    surface_area
+    Ok(surface_area)
 }
 
 fn is_outside(
@@ -125,8 +130,8 @@ fn leads_outside(cubes: &HashSet<Point>, from: &Point, bounds: &(Point, Point))
     false
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let cubes = parse(input).into_iter().collect::<HashSet<Point>>();
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let cubes = parse(input)?.into_iter().collect::<HashSet<Point>>();
 
     let bounds = (
         (
@@ -151,5 +156,21 @@ pub fn solve_part2(input: &str) -> u32 {
         }
     }
 
-    surface_area
+    Ok(surface_area)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
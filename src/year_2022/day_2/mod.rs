@@ -1,9 +1,14 @@
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -11,13 +16,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 15);
+            assert_eq!(result, Ok(15));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 13484);
+            let input = test_input(2022, 2);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(13484));
         }
     }
 
@@ -27,13 +35,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 12);
+            assert_eq!(result, Ok(12));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 13433);
+            let input = test_input(2022, 2);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(13433));
         }
     }
 }
@@ -64,22 +75,39 @@ fn get_outcome(opponent: &GameMove, own: &GameMove) -> GameOutcome {
     }
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+fn parse_rounds(input: &str) -> Result<Vec<(&str, &str)>, SolveError> {
     input
         .lines()
         .map(|line| {
-            let (opponent_move, own_move) = line.split_once(' ').unwrap();
-            let opponent_move = match opponent_move {
-                "A" => GameMove::Rock,
-                "B" => GameMove::Paper,
-                "C" => GameMove::Scissors,
-                _ => panic!("Invalid opponent move"),
-            };
+            line.split_once(' ')
+                .ok_or_else(|| SolveError::parse(input, line, "expected two columns"))
+        })
+        .collect()
+}
+
+fn parse_opponent_move(input: &str, opponent_move: &str) -> Result<GameMove, SolveError> {
+    match opponent_move {
+        "A" => Ok(GameMove::Rock),
+        "B" => Ok(GameMove::Paper),
+        "C" => Ok(GameMove::Scissors),
+        _ => Err(SolveError::parse(
+            input,
+            opponent_move,
+            "invalid opponent move",
+        )),
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    parse_rounds(input)?
+        .into_iter()
+        .map(|(opponent_move, own_move)| {
+            let opponent_move = parse_opponent_move(input, opponent_move)?;
             let own_move = match own_move {
                 "X" => GameMove::Rock,
                 "Y" => GameMove::Paper,
                 "Z" => GameMove::Scissors,
-                _ => panic!("Invalid own move"),
+                _ => return Err(SolveError::parse(input, own_move, "invalid own move")),
             };
 
             let outcome_value = match get_outcome(&opponent_move, &own_move) {
@@ -92,27 +120,27 @@ pub fn solve_part1(input: &str) -> i32 {
                 GameMove::Paper => 2,
                 GameMove::Scissors => 3,
             };
-            outcome_value + own_move_value
+            Ok(outcome_value + own_move_value)
         })
         .sum()
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    input
-        .lines()
-        .map(|line| {
-            let (opponent_move, expected_outcome) = line.split_once(' ').unwrap();
-            let opponent_move = match opponent_move {
-                "A" => GameMove::Rock,
-                "B" => GameMove::Paper,
-                "C" => GameMove::Scissors,
-                _ => panic!("Invalid opponent move"),
-            };
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
+    parse_rounds(input)?
+        .into_iter()
+        .map(|(opponent_move, expected_outcome)| {
+            let opponent_move = parse_opponent_move(input, opponent_move)?;
             let expected_outcome = match expected_outcome {
                 "X" => GameOutcome::Loss,
                 "Y" => GameOutcome::Draw,
                 "Z" => GameOutcome::Win,
-                _ => panic!("Invalid expected outcome"),
+                _ => {
+                    return Err(SolveError::parse(
+                        input,
+                        expected_outcome,
+                        "invalid expected outcome",
+                    ))
+                }
             };
 
             let own_move = match (&opponent_move, &expected_outcome) {
@@ -137,7 +165,23 @@ pub fn solve_part2(input: &str) -> i32 {
                 GameMove::Paper => 2,
                 GameMove::Scissors => 3,
             };
-            outcome_value + own_move_value
+            Ok(outcome_value + own_move_value)
         })
         .sum()
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_rounds(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
@@ -1,17 +1,19 @@
-use nom::{
-    character::complete::{i64, newline},
-    multi::separated_list1,
-    IResult,
-};
+use nom::{character::complete::i64, IResult};
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::VecDeque;
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -19,13 +21,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 3);
+            assert_eq!(result, Ok(3));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 11_037);
+            let input = test_input(2022, 20);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(11_037));
         }
     }
 
@@ -35,13 +40,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 1_623_178_306);
+            assert_eq!(result, Ok(1_623_178_306));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 3_033_720_253_914);
+            let input = test_input(2022, 20);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(3_033_720_253_914));
         }
     }
 }
@@ -49,8 +57,27 @@ mod tests {
 fn line(input: &str) -> IResult<&str, i64> {
     i64(input)
 }
-fn parse(input: &str) -> Vec<i64> {
-    separated_list1(newline, line)(input).unwrap().1
+fn parse(input: &str) -> Result<Vec<i64>, SolveError> {
+    let numbers = parse_all(input, lines(line))?;
+
+    if numbers.len() < 2 {
+        return Err(SolveError::no_solution("need at least two numbers to mix"));
+    }
+
+    Ok(numbers)
+}
+
+fn grove_coordinates(numbers: &[i64], indices: &VecDeque<usize>) -> Result<i64, SolveError> {
+    let zero_idx = numbers
+        .iter()
+        .position(|&x| x == 0)
+        .ok_or_else(|| SolveError::no_solution("no zero in the file"))?;
+    let zero_idx = indices.iter().position(|&x| x == zero_idx).unwrap();
+
+    Ok([1_000, 2_000, 3_000]
+        .iter()
+        .map(|i| numbers[indices[(zero_idx + i) % numbers.len()]])
+        .sum())
 }
 
 fn mix(numbers: &[i64], indices: &mut VecDeque<usize>) {
@@ -74,29 +101,20 @@ fn mix(numbers: &[i64], indices: &mut VecDeque<usize>) {
     }
 }
 
-pub fn solve_part1(input: &str) -> i64 {
-    let numbers = parse(input);
+pub fn solve_part1(input: &str) -> Result<i64, SolveError> {
+    let numbers = parse(input)?;
 
     let mut original_indices: VecDeque<usize> =
         numbers.iter().enumerate().map(|(i, _)| i).collect();
     mix(&numbers, &mut original_indices);
 
-    let zero_idx = numbers.iter().position(|&x| x == 0).unwrap();
-    let zero_idx = original_indices
-        .iter()
-        .position(|&x| x == zero_idx)
-        .unwrap();
-
-    [1_000, 2_000, 3_000]
-        .iter()
-        .map(|i| numbers[original_indices[(zero_idx + i) % numbers.len()]])
-        .sum()
+    grove_coordinates(&numbers, &original_indices)
 }
 
-pub fn solve_part2(input: &str) -> i64 {
+pub fn solve_part2(input: &str) -> Result<i64, SolveError> {
     const KEY: i64 = 811_589_153;
 
-    let numbers = parse(input);
+    let numbers = parse(input)?;
     let numbers = numbers.iter().map(|&n| n * KEY).collect::<Vec<_>>();
 
     let mut original_indices: VecDeque<usize> =
@@ -106,14 +124,21 @@ pub fn solve_part2(input: &str) -> i64 {
         mix(&numbers, &mut original_indices);
     }
 
-    let zero_idx = numbers.iter().position(|&x| x == 0).unwrap();
-    let zero_idx = original_indices
-        .iter()
-        .position(|&x| x == zero_idx)
-        .unwrap();
+    grove_coordinates(&numbers, &original_indices)
+}
 
-    [1000, 2000, 3000]
-        .iter()
-        .map(|i| numbers[original_indices[(zero_idx + i) % numbers.len()]])
-        .sum()
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,24 +1,30 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, i64, newline},
+    character::complete::{alpha1, i64},
     combinator::map,
-    multi::separated_list1,
     sequence::{delimited, terminated},
     IResult,
 };
 use std::collections::HashMap;
 
+use crate::{
+    error::{parse_all, SolveError},
+    memo::Memo,
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     #[test]
     fn it_parses() {
-        let data = parse(EXAMPLE);
+        let data = parse(EXAMPLE).unwrap();
         assert_eq!(
             data,
             HashMap::from_iter([
@@ -41,19 +47,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE.replacen("sjmn", "sjmx", 1);
+        assert_eq!(
+            solve_part1(&input),
+            Err(SolveError::Parse {
+                line: 1,
+                column: 14,
+                message: "unknown monkey sjmx".to_string()
+            })
+        );
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 152);
+            assert_eq!(result, Ok(152));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 84_244_467_642_604);
+            let input = test_input(2022, 21);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(84_244_467_642_604));
         }
     }
 
@@ -63,18 +85,21 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 301);
+            assert_eq!(result, Ok(301));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 3_759_569_926_192);
+            let input = test_input(2022, 21);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(3_759_569_926_192));
         }
     }
 }
 
-fn entry(input: &str) -> IResult<&str, Entry> {
+fn entry(input: &str) -> IResult<&str, Entry<'_>> {
     let (input, left) = alpha1(input)?;
     let (input, op) = delimited(
         tag(" "),
@@ -90,20 +115,42 @@ fn entry(input: &str) -> IResult<&str, Entry> {
             "-" => Entry::Sub(left, right),
             "*" => Entry::Mul(left, right),
             "/" => Entry::Div(left, right),
-            _ => unreachable!(),
+            _ => unreachable!("alt only matches the four operators"),
         },
     ))
 }
-fn line(input: &str) -> IResult<&str, (&str, Entry)> {
+fn line(input: &str) -> IResult<&str, (&str, Entry<'_>)> {
     let (input, name) = terminated(alpha1, tag(": "))(input)?;
     let (input, entry) = alt((map(i64, Entry::Num), entry))(input)?;
 
     Ok((input, (name, entry)))
 }
-fn parse(input: &str) -> HashMap<&str, Entry> {
-    let (_, entries) = separated_list1(newline, line)(input).unwrap();
+fn parse(input: &str) -> Result<HashMap<&str, Entry<'_>>, SolveError> {
+    let entries: HashMap<_, _> = HashMap::from_iter(parse_all(input, lines(line))?);
+
+    for entry in entries.values() {
+        if let Entry::Add(left, right)
+        | Entry::Sub(left, right)
+        | Entry::Mul(left, right)
+        | Entry::Div(left, right) = entry
+        {
+            for name in [left, right] {
+                if !entries.contains_key(name) {
+                    return Err(SolveError::parse(
+                        input,
+                        name,
+                        format!("unknown monkey {name}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    if !entries.contains_key("root") {
+        return Err(SolveError::no_solution("no root monkey"));
+    }
 
-    HashMap::from_iter(entries)
+    Ok(entries)
 }
 
 #[derive(Debug, PartialEq)]
@@ -115,77 +162,83 @@ enum Entry<'a> {
     Num(i64),
 }
 
-fn resolve_monkey(
-    name: &str,
-    data: &HashMap<&str, Entry>,
-    cache: &mut HashMap<String, i64>,
+fn resolve_monkey<'a>(
+    name: &'a str,
+    data: &HashMap<&'a str, Entry<'a>>,
+    memo: &mut Memo<&'a str, i64>,
 ) -> i64 {
-    if let Some(&n) = cache.get(name) {
-        return n;
-    }
-
-    let result = match data.get(name).unwrap() {
+    memo.get_or_insert_with(name, |memo| match data.get(name).unwrap() {
         Entry::Add(left, right) => {
-            resolve_monkey(left, data, cache) + resolve_monkey(right, data, cache)
+            resolve_monkey(left, data, memo) + resolve_monkey(right, data, memo)
         }
         Entry::Sub(left, right) => {
-            resolve_monkey(left, data, cache) - resolve_monkey(right, data, cache)
+            resolve_monkey(left, data, memo) - resolve_monkey(right, data, memo)
         }
         Entry::Mul(left, right) => {
-            resolve_monkey(left, data, cache) * resolve_monkey(right, data, cache)
+            resolve_monkey(left, data, memo) * resolve_monkey(right, data, memo)
         }
         Entry::Div(left, right) => {
-            resolve_monkey(left, data, cache) / resolve_monkey(right, data, cache)
+            resolve_monkey(left, data, memo) / resolve_monkey(right, data, memo)
         }
         Entry::Num(n) => *n,
-    };
-
-    cache.insert(name.to_string(), result);
-
-    result
+    })
 }
 
-pub fn solve_part1(input: &str) -> i64 {
-    let data = parse(input);
-    let mut cache = HashMap::new();
-    resolve_monkey("root", &data, &mut cache)
+pub fn solve_part1(input: &str) -> Result<i64, SolveError> {
+    let data = parse(input)?;
+    let mut memo = Memo::new();
+
+    let root = resolve_monkey("root", &data, &mut memo);
+    memo.trace_stats("resolve");
+    Ok(root)
 }
 
-pub fn solve_part2(input: &str) -> i64 {
-    let data = parse(input);
+pub fn solve_part2(input: &str) -> Result<i64, SolveError> {
+    let data = parse(input)?;
 
     let (left, right) = match data.get("root") {
-        Some(Entry::Add(left, right)) => (left, right),
-        _ => unreachable!(),
+        Some(
+            Entry::Add(left, right)
+            | Entry::Sub(left, right)
+            | Entry::Mul(left, right)
+            | Entry::Div(left, right),
+        ) => (left, right),
+        _ => {
+            return Err(SolveError::no_solution(
+                "root monkey must compare two monkeys",
+            ))
+        }
     };
 
-    let mut needs_humn_cache = HashMap::new();
-    let left_needs_humn = needs_humn(&data, left, &needs_humn_cache);
-
-    let mut resolve_cache = HashMap::new();
+    let mut needs_humn_memo = Memo::new();
+    let mut resolve_memo = Memo::new();
 
+    let left_needs_humn = needs_humn(left, &data, &mut needs_humn_memo);
     let value = if left_needs_humn {
-        resolve_monkey(right, &data, &mut HashMap::new())
+        resolve_monkey(right, &data, &mut resolve_memo)
     } else {
-        resolve_monkey(left, &data, &mut HashMap::new())
+        resolve_monkey(left, &data, &mut resolve_memo)
     };
 
-    resolve_humn(
+    let humn = resolve_humn(
         if left_needs_humn { left } else { right },
         value,
         &data,
-        &mut resolve_cache,
-        &mut needs_humn_cache,
-    )
-    .unwrap()
-}
+        &mut resolve_memo,
+        &mut needs_humn_memo,
+    );
+    resolve_memo.trace_stats("resolve");
+    needs_humn_memo.trace_stats("needs humn");
 
-fn needs_humn(data: &HashMap<&str, Entry>, name: &str, cache: &HashMap<String, bool>) -> bool {
-    if let Some(&needs) = cache.get(name) {
-        return needs;
-    }
+    humn.ok_or_else(|| SolveError::no_solution("humn does not affect the root equality"))
+}
 
-    match data.get(name).unwrap() {
+fn needs_humn<'a>(
+    name: &'a str,
+    data: &HashMap<&'a str, Entry<'a>>,
+    memo: &mut Memo<&'a str, bool>,
+) -> bool {
+    memo.get_or_insert_with(name, |memo| match data.get(name).unwrap() {
         Entry::Add("humn", _) => true,
         Entry::Add(_, "humn") => true,
         Entry::Sub("humn", _) => true,
@@ -195,94 +248,110 @@ fn needs_humn(data: &HashMap<&str, Entry>, name: &str, cache: &HashMap<String, b
         Entry::Div("humn", _) => true,
         Entry::Div(_, "humn") => true,
 
-        Entry::Add(left, right) => needs_humn(data, left, cache) || needs_humn(data, right, cache),
-        Entry::Sub(left, right) => needs_humn(data, left, cache) || needs_humn(data, right, cache),
-        Entry::Mul(left, right) => needs_humn(data, left, cache) || needs_humn(data, right, cache),
-        Entry::Div(left, right) => needs_humn(data, left, cache) || needs_humn(data, right, cache),
+        Entry::Add(left, right) => needs_humn(left, data, memo) || needs_humn(right, data, memo),
+        Entry::Sub(left, right) => needs_humn(left, data, memo) || needs_humn(right, data, memo),
+        Entry::Mul(left, right) => needs_humn(left, data, memo) || needs_humn(right, data, memo),
+        Entry::Div(left, right) => needs_humn(left, data, memo) || needs_humn(right, data, memo),
         Entry::Num(_) => false,
-    }
+    })
 }
 
-fn resolve_humn(
-    name: &str,
+fn resolve_humn<'a>(
+    name: &'a str,
     value: i64,
-    data: &HashMap<&str, Entry>,
-    resolve_cache: &mut HashMap<String, i64>,
-    needs_humn_cache: &mut HashMap<String, bool>,
+    data: &HashMap<&'a str, Entry<'a>>,
+    resolve_memo: &mut Memo<&'a str, i64>,
+    needs_humn_memo: &mut Memo<&'a str, bool>,
 ) -> Option<i64> {
     match data.get(name).unwrap() {
-        Entry::Add("humn", monkey) => Some(value - resolve_monkey(monkey, data, resolve_cache)),
-        Entry::Add(monkey, "humn") => Some(value - resolve_monkey(monkey, data, resolve_cache)),
+        Entry::Add("humn", monkey) => Some(value - resolve_monkey(monkey, data, resolve_memo)),
+        Entry::Add(monkey, "humn") => Some(value - resolve_monkey(monkey, data, resolve_memo)),
 
-        Entry::Sub("humn", monkey) => Some(value + resolve_monkey(monkey, data, resolve_cache)),
-        Entry::Sub(monkey, "humn") => Some(resolve_monkey(monkey, data, resolve_cache) - value),
+        Entry::Sub("humn", monkey) => Some(value + resolve_monkey(monkey, data, resolve_memo)),
+        Entry::Sub(monkey, "humn") => Some(resolve_monkey(monkey, data, resolve_memo) - value),
 
-        Entry::Mul("humn", monkey) => Some(value / resolve_monkey(monkey, data, resolve_cache)),
-        Entry::Mul(monkey, "humn") => Some(value / resolve_monkey(monkey, data, resolve_cache)),
+        Entry::Mul("humn", monkey) => Some(value / resolve_monkey(monkey, data, resolve_memo)),
+        Entry::Mul(monkey, "humn") => Some(value / resolve_monkey(monkey, data, resolve_memo)),
 
-        Entry::Div("humn", monkey) => Some(value * resolve_monkey(monkey, data, resolve_cache)),
-        Entry::Div(monkey, "humn") => Some(resolve_monkey(monkey, data, resolve_cache) / value),
+        Entry::Div("humn", monkey) => Some(value * resolve_monkey(monkey, data, resolve_memo)),
+        Entry::Div(monkey, "humn") => Some(resolve_monkey(monkey, data, resolve_memo) / value),
 
-        Entry::Add(left, right) if needs_humn(data, left, needs_humn_cache) => resolve_humn(
+        Entry::Add(left, right) if needs_humn(left, data, needs_humn_memo) => resolve_humn(
             left,
-            value - resolve_monkey(right, data, resolve_cache),
+            value - resolve_monkey(right, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
-        Entry::Add(left, right) if needs_humn(data, right, needs_humn_cache) => resolve_humn(
+        Entry::Add(left, right) if needs_humn(right, data, needs_humn_memo) => resolve_humn(
             right,
-            value - resolve_monkey(left, data, resolve_cache),
+            value - resolve_monkey(left, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
 
-        Entry::Sub(left, right) if needs_humn(data, left, needs_humn_cache) => resolve_humn(
+        Entry::Sub(left, right) if needs_humn(left, data, needs_humn_memo) => resolve_humn(
             left,
-            value + resolve_monkey(right, data, resolve_cache),
+            value + resolve_monkey(right, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
-        Entry::Sub(left, right) if needs_humn(data, right, needs_humn_cache) => resolve_humn(
+        Entry::Sub(left, right) if needs_humn(right, data, needs_humn_memo) => resolve_humn(
             right,
-            resolve_monkey(left, data, resolve_cache) - value,
+            resolve_monkey(left, data, resolve_memo) - value,
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
 
-        Entry::Mul(left, right) if needs_humn(data, left, needs_humn_cache) => resolve_humn(
+        Entry::Mul(left, right) if needs_humn(left, data, needs_humn_memo) => resolve_humn(
             left,
-            value / resolve_monkey(right, data, resolve_cache),
+            value / resolve_monkey(right, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
-        Entry::Mul(left, right) if needs_humn(data, right, needs_humn_cache) => resolve_humn(
+        Entry::Mul(left, right) if needs_humn(right, data, needs_humn_memo) => resolve_humn(
             right,
-            value / resolve_monkey(left, data, resolve_cache),
+            value / resolve_monkey(left, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
 
-        Entry::Div(left, right) if needs_humn(data, left, needs_humn_cache) => resolve_humn(
+        Entry::Div(left, right) if needs_humn(left, data, needs_humn_memo) => resolve_humn(
             left,
-            value * resolve_monkey(right, data, resolve_cache),
+            value * resolve_monkey(right, data, resolve_memo),
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
-        Entry::Div(left, right) if needs_humn(data, right, needs_humn_cache) => resolve_humn(
+        Entry::Div(left, right) if needs_humn(right, data, needs_humn_memo) => resolve_humn(
             right,
-            resolve_monkey(left, data, resolve_cache) / value,
+            resolve_monkey(left, data, resolve_memo) / value,
             data,
-            resolve_cache,
-            needs_humn_cache,
+            resolve_memo,
+            needs_humn_memo,
         ),
 
         _ => None,
     }
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
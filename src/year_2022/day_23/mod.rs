@@ -1,42 +1,52 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use crate::{
+    error::SolveError,
+    geometry::Point2,
+    solution::{Answer, Solution},
+    visualize::{self, Frame},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     #[test]
     fn it_parses() {
-        let data = parse(EXAMPLE);
+        let data = parse(EXAMPLE).unwrap();
         assert_eq!(
             data,
-            HashSet::from_iter([
-                (5, 5),
-                (4, 1),
-                (1, 6),
-                (6, 2),
-                (6, 5),
-                (4, 0),
-                (5, 3),
-                (0, 5),
-                (3, 1),
-                (0, 2),
-                (0, 4),
-                (6, 3),
-                (1, 5),
-                (4, 6),
-                (4, 4),
-                (1, 3),
-                (6, 1),
-                (2, 1),
-                (3, 4),
-                (4, 2),
-                (2, 4),
-                (3, 5)
-            ])
+            HashSet::from_iter(
+                [
+                    (5, 5),
+                    (4, 1),
+                    (1, 6),
+                    (6, 2),
+                    (6, 5),
+                    (4, 0),
+                    (5, 3),
+                    (0, 5),
+                    (3, 1),
+                    (0, 2),
+                    (0, 4),
+                    (6, 3),
+                    (1, 5),
+                    (4, 6),
+                    (4, 4),
+                    (1, 3),
+                    (6, 1),
+                    (2, 1),
+                    (3, 4),
+                    (4, 2),
+                    (2, 4),
+                    (3, 5)
+                ]
+                .map(Point2::from)
+            )
         );
     }
 
@@ -46,13 +56,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 110);
+            assert_eq!(result, Ok(110));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 3_862);
+            let input = test_input(2022, 23);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(3_862));
         }
     }
 
@@ -62,70 +75,63 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 20);
+            assert_eq!(result, Ok(20));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 913);
+            let input = test_input(2022, 23);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(913));
         }
     }
 }
 
-type Position = (i32, i32);
-
-fn parse(input: &str) -> HashSet<Position> {
+fn parse(input: &str) -> Result<HashSet<Point2>, SolveError> {
     let mut map = HashSet::new();
 
     for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                map.insert((x as i32, y as i32));
+        for (x, c) in line.char_indices() {
+            match c {
+                '#' => {
+                    map.insert(Point2::new(x as i64, y as i64));
+                }
+                '.' => {}
+                _ => return Err(SolveError::parse(input, &line[x..], "expected '#' or '.'")),
             }
         }
     }
 
-    map
+    if map.is_empty() {
+        return Err(SolveError::no_solution("no elves on the map"));
+    }
+
+    Ok(map)
 }
 
-const ALL_NEIGHBORS: [Position; 8] = [
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-    (-1, -1),
-];
+fn draw(map: &HashSet<Point2>) -> Frame {
+    Frame::sparse(map.iter().map(|&elf| (elf, '#')), '.')
+}
 
-const INITIAL_PRIORITIES: [[Position; 3]; 4] = [
-    [(0, -1), (1, -1), (-1, -1)],
-    [(0, 1), (1, 1), (-1, 1)],
-    [(-1, 0), (-1, -1), (-1, 1)],
-    [(1, 0), (1, -1), (1, 1)],
+const INITIAL_PRIORITIES: [[Point2; 3]; 4] = [
+    [Point2::new(0, -1), Point2::new(1, -1), Point2::new(-1, -1)],
+    [Point2::new(0, 1), Point2::new(1, 1), Point2::new(-1, 1)],
+    [Point2::new(-1, 0), Point2::new(-1, -1), Point2::new(-1, 1)],
+    [Point2::new(1, 0), Point2::new(1, -1), Point2::new(1, 1)],
 ];
 
-fn step(map: &mut HashSet<Position>, priorities: &VecDeque<[(i32, i32); 3]>) -> bool {
-    let mut moves = HashMap::<Position, Position>::new();
-
-    for &(x, y) in map.iter() {
-        if ALL_NEIGHBORS
-            .iter()
-            .map(|(dx, dy)| (x + dx, y + dy))
-            .any(|(x, y)| map.contains(&(x, y)))
-        {
-            if let Some(priority) = priorities.iter().find(|priority| {
-                priority
-                    .iter()
-                    .map(|(dx, dy)| (x + dx, y + dy))
-                    .all(|(x, y)| !map.contains(&(x, y)))
-            }) {
-                moves.insert(
-                    (x, y),
-                    priority.first().map(|(dx, dy)| (x + dx, y + dy)).unwrap(),
-                );
+fn step(map: &mut HashSet<Point2>, priorities: &VecDeque<[Point2; 3]>) -> bool {
+    let mut moves = HashMap::<Point2, Point2>::new();
+
+    for &elf in map.iter() {
+        if elf.neighbors8().any(|pos| map.contains(&pos)) {
+            if let Some(priority) = priorities
+                .iter()
+                .find(|priority| priority.iter().all(|&delta| !map.contains(&(elf + delta))))
+            {
+                moves.insert(elf, elf + priority[0]);
             }
         }
     }
@@ -145,35 +151,55 @@ fn step(map: &mut HashSet<Position>, priorities: &VecDeque<[(i32, i32); 3]>) ->
     !moves.is_empty()
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let mut map = parse(input);
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let mut map = parse(input)?;
     let mut priorities = VecDeque::from_iter(INITIAL_PRIORITIES);
 
+    visualize::frame(|| draw(&map));
     for _ in 0..10 {
         step(&mut map, &priorities);
         priorities.rotate_left(1);
+        visualize::frame(|| draw(&map));
     }
 
-    let (min_x, max_x) = map.iter().map(|&(x, _)| x).minmax().into_option().unwrap();
-    let (min_y, max_y) = map.iter().map(|&(_, y)| y).minmax().into_option().unwrap();
+    let (min_x, max_x) = map.iter().map(|elf| elf.x).minmax().into_option().unwrap();
+    let (min_y, max_y) = map.iter().map(|elf| elf.y).minmax().into_option().unwrap();
     let width = (max_x - min_x + 1) as u32;
     let height = (max_y - min_y + 1) as u32;
 
-    width * height - map.len() as u32
+    Ok(width * height - map.len() as u32)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let mut map = parse(input);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let mut map = parse(input)?;
     let mut priorities = VecDeque::from_iter(INITIAL_PRIORITIES);
     let mut round = 0;
 
+    visualize::frame(|| draw(&map));
     loop {
         round += 1;
         if !step(&mut map, &priorities) {
             break;
         }
         priorities.rotate_left(1);
+        visualize::frame(|| draw(&map));
     }
 
-    round
+    Ok(round)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
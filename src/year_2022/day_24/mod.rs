@@ -1,11 +1,42 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
+
+use crate::{
+    error::SolveError,
+    geometry::{Direction, Point2},
+    grid::Grid,
+    search::bfs,
+    solution::{Answer, Solution},
+    visualize::{self, Frame},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_draws_the_expedition() {
+        let (result, frames) = visualize::record(1, || solve_part2(EXAMPLE));
+
+        assert_eq!(result, Ok(54));
+        // One frame per minute of each leg, counting the starting minute
+        assert_eq!(frames.len(), 19 + 24 + 14);
+        assert_eq!(
+            frames[0].to_string(),
+            "#E######\n\
+             #>>.<^<#\n\
+             #.<..<<#\n\
+             #>v.><>#\n\
+             #<^v^^>#\n\
+             ######.#\n"
+        );
+        assert_eq!(
+            frames.last().unwrap().to_string().lines().nth(5),
+            Some("######E#")
+        );
+    }
 
     #[test]
     fn it_finds_initial_position_of_blizzard() {
@@ -14,22 +45,70 @@ mod tests {
 
         use Direction::*;
 
-        assert_eq!(rewind((0, 0), Up, width, height, 1), (0, 1));
-        assert_eq!(rewind((0, 0), Up, width, height, 2), (0, 2));
-        assert_eq!(rewind((0, 0), Up, width, height, 0), (0, 0));
-        assert_eq!(rewind((0, 0), Up, width, height, height), (0, 0));
-        assert_eq!(rewind((0, 0), Down, width, height, 1), (0, 3));
-        assert_eq!(rewind((0, 0), Down, width, height, 2), (0, 2));
-        assert_eq!(rewind((0, 0), Down, width, height, 0), (0, 0));
-        assert_eq!(rewind((0, 0), Down, width, height, height), (0, 0));
-        assert_eq!(rewind((0, 0), Left, width, height, 1), (1, 0));
-        assert_eq!(rewind((0, 0), Left, width, height, 2), (2, 0));
-        assert_eq!(rewind((0, 0), Left, width, height, 0), (0, 0));
-        assert_eq!(rewind((0, 0), Left, width, height, width), (0, 0));
-        assert_eq!(rewind((0, 0), Right, width, height, 1), (3, 0));
-        assert_eq!(rewind((0, 0), Right, width, height, 2), (2, 0));
-        assert_eq!(rewind((0, 0), Right, width, height, 0), (0, 0));
-        assert_eq!(rewind((0, 0), Right, width, height, width), (0, 0));
+        assert_eq!(
+            rewind(Point2::new(0, 0), Up, width, height, 1),
+            Point2::new(0, 1)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Up, width, height, 2),
+            Point2::new(0, 2)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Up, width, height, 0),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Up, width, height, height),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Down, width, height, 1),
+            Point2::new(0, 3)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Down, width, height, 2),
+            Point2::new(0, 2)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Down, width, height, 0),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Down, width, height, height),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Left, width, height, 1),
+            Point2::new(1, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Left, width, height, 2),
+            Point2::new(2, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Left, width, height, 0),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Left, width, height, width),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Right, width, height, 1),
+            Point2::new(3, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Right, width, height, 2),
+            Point2::new(2, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Right, width, height, 0),
+            Point2::new(0, 0)
+        );
+        assert_eq!(
+            rewind(Point2::new(0, 0), Right, width, height, width),
+            Point2::new(0, 0)
+        );
     }
 
     mod part1 {
@@ -38,13 +117,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 18);
+            assert_eq!(result, Ok(18));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 279);
+            let input = test_input(2022, 24);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(279));
         }
     }
 
@@ -54,45 +136,33 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 54);
+            assert_eq!(result, Ok(54));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 762);
-        }
-    }
-}
-
-type Position = (i32, i32);
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+            let input = test_input(2022, 24);
 
-impl Direction {
-    fn reversed(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Down,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-            Direction::Right => Direction::Left,
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(762));
         }
     }
 }
 
-fn parse(input: &str) -> (u32, u32, HashMap<Position, Direction>) {
-    let mut blizzards: HashMap<Position, Direction> = HashMap::new();
+fn parse(input: &str) -> Result<(u32, u32, HashMap<Point2, Direction>), SolveError> {
+    let mut blizzards: HashMap<Point2, Direction> = HashMap::new();
 
     let mut width = 0;
     let mut height = 0;
 
     let lines = input.lines().collect::<Vec<_>>();
+    if lines.len() < 3 {
+        return Err(SolveError::parse(input, input, "expected a walled valley"));
+    }
+    if let Some(line) = lines.iter().find(|line| line.chars().count() < 3) {
+        return Err(SolveError::parse(input, line, "expected a walled row"));
+    }
 
     for (y, line) in lines[1..lines.len() - 1].iter().enumerate() {
         height = height.max(y as u32 + 1);
@@ -102,155 +172,152 @@ fn parse(input: &str) -> (u32, u32, HashMap<Position, Direction>) {
         for (x, c) in chars[1..chars.len() - 1].iter().enumerate() {
             width = width.max(x as u32 + 1);
 
-            let pos = (x as i32, y as i32);
+            let pos = Point2::new(x as i64, y as i64);
 
             let dir = match c {
                 '^' => Direction::Up,
                 'v' => Direction::Down,
                 '<' => Direction::Left,
                 '>' => Direction::Right,
-                _ => continue,
+                '.' => continue,
+                _ => {
+                    let (i, _) = line.char_indices().nth(x + 1).unwrap();
+                    return Err(SolveError::parse(
+                        input,
+                        &line[i..],
+                        "expected a blizzard or open ground",
+                    ));
+                }
             };
 
             blizzards.insert(pos, dir);
         }
     }
 
-    (width, height, blizzards)
-}
-
-fn rewind((x, y): Position, dir: Direction, width: u32, height: u32, time: u32) -> Position {
-    let reversed = dir.reversed();
-    let (dx, dy) = (
-        match reversed {
-            Direction::Left => -1,
-            Direction::Right => 1,
-            _ => 0,
-        },
-        match reversed {
-            Direction::Up => -1,
-            Direction::Down => 1,
-            _ => 0,
-        },
-    );
-
-    let (x, y) = (
-        (x + dx * time as i32) % width as i32,
-        (y + dy * time as i32) % height as i32,
-    );
-
-    (
-        if x < 0 { x + width as i32 } else { x },
-        if y < 0 { y + height as i32 } else { y },
-    )
+    Ok((width, height, blizzards))
 }
 
-fn neighbors(pos: Position, width: u32, height: u32, end: Position) -> Vec<Position> {
-    let (x, y) = pos;
+// Where a blizzard heading in `dir` must have started to be at `pos` after `time`
+fn rewind(pos: Point2, dir: Direction, width: u32, height: u32, time: u32) -> Point2 {
+    let Point2 { x, y } = pos + dir.opposite() * time as i64;
 
-    let mut result = Vec::new();
+    Point2::new(x.rem_euclid(width as i64), y.rem_euclid(height as i64))
+}
 
-    for (dx, dy) in &[(0, -1), (0, 1), (-1, 0), (1, 0)] {
-        let (x, y) = (x + dx, y + dy);
+// The valley at `time`, walls included, with the expedition at `pos`
+fn draw(
+    blizzards: &HashMap<Point2, Direction>,
+    width: u32,
+    height: u32,
+    (start, end): (Point2, Point2),
+    pos: Point2,
+    time: u32,
+) -> Frame {
+    let mut frame = Grid::filled(width as usize + 2, height as usize + 2, '#');
+    let mut set = |Point2 { x, y }: Point2, c| frame[((x + 1) as usize, (y + 1) as usize)] = c;
 
-        if (x >= 0 && y >= 0 && x < width as i32 && y < height as i32) || (x, y) == end {
-            result.push((x, y));
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            set(Point2::new(x, y), '.');
         }
     }
+    set(start, '.');
+    set(end, '.');
+
+    let mut counts = HashMap::<Point2, (u32, Direction)>::new();
+    for (&from, &dir) in blizzards {
+        let Point2 { x, y } = from + Point2::from(dir) * time as i64;
+        let at = Point2::new(x.rem_euclid(width as i64), y.rem_euclid(height as i64));
+        counts.entry(at).or_insert((0, dir)).0 += 1;
+    }
+    for (at, (count, dir)) in counts {
+        let c = match (count, dir) {
+            (1, Direction::Up) => '^',
+            (1, Direction::Down) => 'v',
+            (1, Direction::Left) => '<',
+            (1, Direction::Right) => '>',
+            (count, _) => char::from_digit(count, 10).unwrap(),
+        };
+        set(at, c);
+    }
+    set(pos, 'E');
 
-    result
+    Frame::new(frame)
+}
+
+fn neighbors(pos: Point2, width: u32, height: u32, end: Point2) -> Vec<Point2> {
+    pos.neighbors4()
+        .filter(|&next| {
+            (next.x >= 0 && next.y >= 0 && next.x < width as i64 && next.y < height as i64)
+                || next == end
+        })
+        .collect()
 }
 
 fn can_move(
-    blizzards: &HashMap<Position, Direction>,
-    pos: Position,
+    blizzards: &HashMap<Point2, Direction>,
+    pos: Point2,
     time: u32,
     width: u32,
     height: u32,
-    start: Position,
-    end: Position,
+    start: Point2,
+    end: Point2,
 ) -> bool {
-    use Direction::*;
-
-    let possible_up_blizzard = blizzards
-        .get(&rewind(pos, Up, width, height, time))
-        .filter(|x| **x == Up);
-    let possible_down_blizzard = blizzards
-        .get(&rewind(pos, Down, width, height, time))
-        .filter(|x| **x == Down);
-    let possible_left_blizzard = blizzards
-        .get(&rewind(pos, Left, width, height, time))
-        .filter(|x| **x == Left);
-    let possible_right_blizzard = blizzards
-        .get(&rewind(pos, Right, width, height, time))
-        .filter(|x| **x == Right);
-
     pos == end
         || pos == start
-        || [
-            possible_up_blizzard,
-            possible_down_blizzard,
-            possible_left_blizzard,
-            possible_right_blizzard,
-        ]
-        .iter()
-        .all(|o| o.is_none())
+        || Direction::ALL
+            .into_iter()
+            .all(|dir| blizzards.get(&rewind(pos, dir, width, height, time)) != Some(&dir))
 }
 
 fn get_best_time(
-    blizzards: &HashMap<Position, Direction>,
+    blizzards: &HashMap<Point2, Direction>,
     width: u32,
     height: u32,
-    start: Position,
-    end: Position,
+    start: Point2,
+    end: Point2,
     start_time: u32,
-) -> u32 {
-    let mut visited: HashSet<(Position, u32)> = HashSet::from_iter([(start, start_time)]);
-    let mut q: VecDeque<(Position, u32)> = VecDeque::new();
-    q.push_front((start, start_time));
-
-    while let Some((pos, time)) = q.pop_front() {
-        if pos == end {
-            return time;
-        }
+) -> Result<u32, SolveError> {
+    let successors = |&(pos, time): &(Point2, u32)| {
         let next_time = time + 1;
-        for next_pos in neighbors(pos, width, height, end) {
-            if visited.contains(&(next_pos, next_time)) {
-                continue;
-            }
-
-            if can_move(blizzards, next_pos, next_time, width, height, start, end) {
-                visited.insert((next_pos, next_time));
-                q.push_back((next_pos, next_time));
-            }
-        }
-        if can_move(blizzards, pos, next_time, width, height, start, end) {
-            visited.insert((pos, next_time));
-            q.push_back((pos, next_time));
-        }
+
+        neighbors(pos, width, height, end)
+            .into_iter()
+            .chain([pos])
+            .filter(move |&next_pos| {
+                can_move(blizzards, next_pos, next_time, width, height, start, end)
+            })
+            .map(move |next_pos| (next_pos, next_time))
+    };
+
+    let path = bfs((start, start_time), successors, |&(pos, _)| pos == end)
+        .ok_or_else(|| SolveError::no_solution("the blizzards block every path"))?;
+
+    for &(pos, time) in &path.states {
+        visualize::frame(|| draw(blizzards, width, height, (start, end), pos, time));
     }
 
-    unreachable!();
+    Ok(start_time + path.cost as u32)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (width, height, blizzards) = parse(input);
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let (width, height, blizzards) = parse(input)?;
 
-    let start = (0, -1);
-    let end = (width as i32 - 1, height as i32);
+    let start = Point2::new(0, -1);
+    let end = Point2::new(width as i64 - 1, height as i64);
 
     get_best_time(&blizzards, width, height, start, end, 0)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (width, height, blizzards) = parse(input);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let (width, height, blizzards) = parse(input)?;
 
-    let start = (0, -1);
-    let end = (width as i32 - 1, height as i32);
+    let start = Point2::new(0, -1);
+    let end = Point2::new(width as i64 - 1, height as i64);
 
-    let time_to_reach_goal = get_best_time(&blizzards, width, height, start, end, 0);
+    let time_to_reach_goal = get_best_time(&blizzards, width, height, start, end, 0)?;
     let time_to_get_back_for_snacks =
-        get_best_time(&blizzards, width, height, end, start, time_to_reach_goal);
+        get_best_time(&blizzards, width, height, end, start, time_to_reach_goal)?;
     get_best_time(
         &blizzards,
         width,
@@ -260,3 +327,19 @@ pub fn solve_part2(input: &str) -> u32 {
         time_to_get_back_for_snacks,
     )
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
@@ -1,12 +1,41 @@
 use itertools::Itertools;
 use std::collections::VecDeque;
 
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
+    use proptest::prelude::*;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    // Canonical SNAFU numbers, i.e. without leading zeros, small enough to fit in a u64
+    fn snafu() -> impl Strategy<Value = String> {
+        prop_oneof![Just("0".to_string()), "[12][=\\-012]{0,25}"]
+    }
+
+    proptest! {
+        #[test]
+        fn it_round_trips_snafu(snafu in snafu()) {
+            prop_assert_eq!(u64_to_snafu(snafu_to_u64(&snafu)), snafu);
+        }
+
+        #[test]
+        fn it_round_trips_u64(n in 0..1u64 << 58) {
+            prop_assert_eq!(snafu_to_u64(&u64_to_snafu(n)), n);
+        }
+
+        #[test]
+        fn it_sums_snafu_lines(numbers in prop::collection::vec(0..1u64 << 48, 1..20)) {
+            let input = numbers.iter().map(|&n| u64_to_snafu(n)).join("\n");
+            prop_assert_eq!(solve_part1(&input), Ok(u64_to_snafu(numbers.iter().sum())));
+        }
+    }
 
     #[test]
     fn it_converts_snafu_to_u64() {
@@ -66,13 +95,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, "2=-1=0");
+            assert_eq!(result, Ok("2=-1=0".to_string()));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, "2=10---0===-1--01-20");
+            let input = test_input(2022, 25);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok("2=10---0===-1--01-20".to_string()));
         }
     }
 }
@@ -121,6 +153,31 @@ fn u64_to_snafu(mut n: u64) -> String {
     snafu.iter().join("")
 }
 
-pub fn solve_part1(input: &str) -> String {
-    u64_to_snafu(input.lines().map(snafu_to_u64).sum())
+fn parse_snafu(input: &str, line: &str) -> Result<u64, SolveError> {
+    match line.find(|c| !matches!(c, '=' | '-' | '0' | '1' | '2')) {
+        Some(i) => Err(SolveError::parse(input, &line[i..], "invalid SNAFU digit")),
+        None => Ok(snafu_to_u64(line)),
+    }
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<u64>, SolveError> {
+    input.lines().map(|line| parse_snafu(input, line)).collect()
+}
+
+pub fn solve_part1(input: &str) -> Result<String, SolveError> {
+    let sum = parse_numbers(input)?.into_iter().sum();
+
+    Ok(u64_to_snafu(sum))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_numbers(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
 }
@@ -1,11 +1,16 @@
 use std::collections::HashSet;
 
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -13,13 +18,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 157);
+            assert_eq!(result, Ok(157));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 7824);
+            let input = test_input(2022, 3);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(7824));
         }
     }
 
@@ -29,20 +37,46 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 70);
+            assert_eq!(result, Ok(70));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 2798);
+            let input = test_input(2022, 3);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(2798));
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+fn priority(input: &str, line: &str, c: char) -> Result<i32, SolveError> {
+    match c {
+        'a'..='z' => Ok(c as i32 - 'a' as i32 + 1),
+        'A'..='Z' => Ok(c as i32 - 'A' as i32 + 27),
+        _ => Err(SolveError::parse(
+            input,
+            line,
+            format!("invalid item {c:?}"),
+        )),
+    }
+}
+
+fn parse_rucksacks(input: &str) -> Result<Vec<&str>, SolveError> {
     input
         .lines()
+        .map(|line| {
+            line.chars()
+                .try_for_each(|c| priority(input, line, c).map(|_| ()))
+                .map(|()| line)
+        })
+        .collect()
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    parse_rucksacks(input)?
+        .into_iter()
         .flat_map(|line| {
             let (first, second) = line.split_at(line.len() / 2);
 
@@ -51,36 +85,39 @@ pub fn solve_part1(input: &str) -> i32 {
 
             seen_first
                 .intersection(&seen_second)
-                .map(|c| -> i32 {
-                    if c.is_ascii_lowercase() {
-                        *c as i32 - 'a' as i32 + 1
-                    } else {
-                        *c as i32 - 'A' as i32 + 27
-                    }
-                })
-                .collect::<Vec<i32>>()
+                .map(|c| priority(input, line, *c))
+                .collect::<Vec<_>>()
         })
         .sum()
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    input
-        .lines()
-        .collect::<Vec<&str>>()
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
+    parse_rucksacks(input)?
         .chunks(3)
         .flat_map(|chunk| {
             chunk
                 .iter()
                 .map(|line| HashSet::<_>::from_iter(line.chars()))
                 .reduce(|acc, x| acc.intersection(&x).copied().collect())
-                .unwrap()
-        })
-        .map(|c| -> i32 {
-            if c.is_ascii_lowercase() {
-                c as i32 - 'a' as i32 + 1
-            } else {
-                c as i32 - 'A' as i32 + 27
-            }
+                .unwrap_or_default()
+                .into_iter()
+                .map(|c| priority(input, chunk[0], c))
         })
         .sum()
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_rucksacks(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
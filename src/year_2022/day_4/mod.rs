@@ -1,11 +1,15 @@
-use std::ops::RangeInclusive;
+use crate::{
+    error::{parse_number, SolveError},
+    ranges::RangeSet,
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -13,13 +17,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 2);
+            assert_eq!(result, Ok(2));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 424);
+            let input = test_input(2022, 4);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(424));
         }
     }
 
@@ -29,58 +36,81 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 4);
+            assert_eq!(result, Ok(4));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 804);
+            let input = test_input(2022, 4);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(804));
         }
     }
 }
 
-fn contains(a: &RangeInclusive<i32>, b: &RangeInclusive<i32>) -> bool {
-    a.start() <= b.start() && b.end() <= a.end() || b.start() <= a.start() && a.end() <= b.end()
+fn contains(a: &RangeSet<i32>, b: &RangeSet<i32>) -> bool {
+    a.is_superset(b) || b.is_superset(a)
 }
 
-fn overlaps(a: &RangeInclusive<i32>, b: &RangeInclusive<i32>) -> bool {
-    a.start() <= b.start() && b.start() <= a.end() || b.start() <= a.start() && a.start() <= b.end()
+fn overlaps(a: &RangeSet<i32>, b: &RangeSet<i32>) -> bool {
+    !a.intersection(b).is_empty()
 }
 
-fn parse_elf_range(range: &str) -> RangeInclusive<i32> {
-    range
+fn parse_elf_range(input: &str, range: &str) -> Result<RangeSet<i32>, SolveError> {
+    let (a, b) = range
         .split_once('-')
-        .map(|(a, b)| {
-            let a = a.parse::<i32>().unwrap();
-            let b = b.parse::<i32>().unwrap();
-            a..=b
-        })
-        .unwrap()
+        .ok_or_else(|| SolveError::parse(input, range, "expected a range"))?;
+    let a = parse_number::<i32>(input, a)?;
+    let b = parse_number::<i32>(input, b)?;
+
+    Ok(RangeSet::from(a..=b))
 }
 
-fn parse_input_line(line: &str) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
-    line.split_once(',')
-        .map(|(a, b)| {
-            let a = parse_elf_range(a);
-            let b = parse_elf_range(b);
-            (a, b)
-        })
-        .unwrap()
+type Pair = (RangeSet<i32>, RangeSet<i32>);
+
+fn parse_input_line(input: &str, line: &str) -> Result<Pair, SolveError> {
+    let (a, b) = line
+        .split_once(',')
+        .ok_or_else(|| SolveError::parse(input, line, "expected two ranges"))?;
+
+    Ok((parse_elf_range(input, a)?, parse_elf_range(input, b)?))
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+fn parse_pairs(input: &str) -> Result<Vec<Pair>, SolveError> {
     input
         .lines()
-        .map(parse_input_line)
+        .map(|line| parse_input_line(input, line))
+        .collect()
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    Ok(parse_pairs(input)?
+        .iter()
         .filter(|(a, b)| contains(a, b))
-        .count() as i32
+        .count() as i32)
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    input
-        .lines()
-        .map(parse_input_line)
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
+    Ok(parse_pairs(input)?
+        .iter()
         .filter(|(a, b)| overlaps(a, b))
-        .count() as i32
+        .count() as i32)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_pairs(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,11 +1,36 @@
-use std::str::FromStr;
+use nom::{bytes::complete::is_not, combinator::recognize, sequence::separated_pair, IResult};
+
+use crate::{
+    error::{parse_all, parse_number, SolveError},
+    parsers::{blank_line, lines},
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_accepts_crlf_line_endings() {
+        let input = EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(solve_part1(&input), Ok("CMZ".to_string()));
+        assert_eq!(solve_part2(&input), Ok("MCD".to_string()));
+    }
+
+    #[test]
+    fn it_reports_missing_instructions() {
+        assert_eq!(
+            solve_part1("    [D]\n[N] [C]\n 1   2\n"),
+            Err(SolveError::Parse {
+                line: 4,
+                column: 1,
+                message: "expected a line break".to_string()
+            })
+        );
+    }
 
     mod part1 {
         use super::*;
@@ -13,13 +38,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, "CMZ");
+            assert_eq!(result, Ok("CMZ".to_string()));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, "JDTMRWCQJ");
+            let input = test_input(2022, 5);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok("JDTMRWCQJ".to_string()));
         }
     }
 
@@ -29,42 +57,43 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, "MCD");
+            assert_eq!(result, Ok("MCD".to_string()));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, "VHJDDCWRD");
+            let input = test_input(2022, 5);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok("VHJDDCWRD".to_string()));
         }
     }
 }
 
-fn parse_stacks(input: &str) -> Vec<Vec<char>> {
-    let mut stacks = Vec::<Vec<char>>::new();
-    let mut lines = input.lines().rev();
-
-    let legend = lines.next().unwrap();
+fn parse_stacks(input: &str, stacks: &str) -> Result<Vec<Vec<char>>, SolveError> {
+    let mut lines = stacks.lines().rev();
+    let legend = lines
+        .next()
+        .ok_or_else(|| SolveError::parse(input, stacks, "missing stack legend"))?;
     let legend = legend
         .char_indices()
         .filter(|(_, c)| *c != ' ')
         .map(|(i, _)| i)
         .collect::<Vec<usize>>();
 
-    for _ in &legend {
-        stacks.push(vec![]);
-    }
+    let mut stacks = vec![Vec::<char>::new(); legend.len()];
 
     for line in lines {
         for (stack_num, line_idx) in legend.iter().enumerate() {
-            let c = line.chars().nth(*line_idx).unwrap();
-
+            let c = line.chars().nth(*line_idx).unwrap_or(' ');
             if c != ' ' {
-                stacks.get_mut(stack_num).unwrap().push(c);
+                stacks[stack_num].push(c);
             }
         }
     }
-    stacks
+
+    Ok(stacks)
 }
 
 #[derive(Debug)]
@@ -74,64 +103,103 @@ struct Instruction {
     to: usize,
 }
 
-impl FromStr for Instruction {
-    type Err = ();
+fn parse_instruction(input: &str, line: &str, stacks: usize) -> Result<Instruction, SolveError> {
+    let words = line.split(' ').collect::<Vec<_>>();
+    let [_, count, _, from, _, to] = words[..] else {
+        return Err(SolveError::parse(
+            input,
+            line,
+            "expected \"move N from N to N\"",
+        ));
+    };
+
+    let count = parse_number::<usize>(input, count)?;
+    let stack = |s: &str| match parse_number::<usize>(input, s)? {
+        n @ 1.. if n <= stacks => Ok(n - 1),
+        _ => Err(SolveError::parse(input, s, "no such stack")),
+    };
+
+    Ok(Instruction {
+        count,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (_, input) = input.split_once(' ').unwrap();
-        let (count, input) = input.split_once(' ').unwrap();
-        let (_, input) = input.split_once(' ').unwrap();
-        let (from, input) = input.split_once(' ').unwrap();
-        let (_, input) = input.split_once(' ').unwrap();
+// The drawing of the stacks, then the instructions after a blank line
+fn sections(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    separated_pair(
+        recognize(lines(is_not("\r\n"))),
+        blank_line,
+        lines(is_not("\r\n")),
+    )(input)
+}
 
-        let count = count.parse::<usize>().unwrap();
-        let from = from.parse::<usize>().unwrap() - 1;
-        let to = input.parse::<usize>().unwrap() - 1;
+fn parse(input: &str) -> Result<(Vec<Vec<char>>, Vec<Instruction>), SolveError> {
+    let (stacks, instructions) = parse_all(input, sections)?;
+    let stacks = parse_stacks(input, stacks)?;
+    let instructions = instructions
+        .into_iter()
+        .map(|line| parse_instruction(input, line, stacks.len()))
+        .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Instruction { count, from, to })
-    }
+    Ok((stacks, instructions))
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|line| Instruction::from_str(line).unwrap())
-        .collect::<Vec<Instruction>>()
+fn move_crates(stacks: &mut [Vec<char>], from: usize, to: usize) -> Result<char, SolveError> {
+    let item = stacks[from]
+        .pop()
+        .ok_or_else(|| SolveError::no_solution(format!("stack {} is empty", from + 1)))?;
+    stacks[to].push(item);
+    Ok(item)
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let (stacks, instructions) = input.split_once("\n\n").unwrap();
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|s| s.last()).collect::<String>()
+}
 
-    let mut stacks = parse_stacks(stacks);
-    let instructions = parse_instructions(instructions);
+pub fn solve_part1(input: &str) -> Result<String, SolveError> {
+    let (mut stacks, instructions) = parse(input)?;
 
     for Instruction { count, from, to } in instructions {
         for _ in 0..count {
-            let item = stacks[from].pop().unwrap();
-            stacks[to].push(item);
+            move_crates(&mut stacks, from, to)?;
         }
     }
 
-    stacks.iter().map(|s| s.last().unwrap()).collect::<String>()
+    Ok(top_crates(&stacks))
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let (stacks, instructions) = input.split_once("\n\n").unwrap();
-
-    let mut stacks = parse_stacks(stacks);
-    let instructions = parse_instructions(instructions);
+pub fn solve_part2(input: &str) -> Result<String, SolveError> {
+    let (mut stacks, instructions) = parse(input)?;
 
     for Instruction { count, from, to } in instructions {
-        let mut temp_stack = Vec::<char>::new();
-
-        for _ in 0..count {
-            temp_stack.push(stacks.get_mut(from).unwrap().pop().unwrap());
+        let height = stacks[from].len();
+        if height < count {
+            return Err(SolveError::no_solution(format!(
+                "stack {} is too short",
+                from + 1
+            )));
         }
+        let moved = stacks[from].split_off(height - count);
+        stacks[to].extend(moved);
+    }
 
-        for _ in 0..count {
-            stacks.get_mut(to).unwrap().push(temp_stack.pop().unwrap());
-        }
+    Ok(top_crates(&stacks))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
     }
 
-    stacks.iter().map(|s| s.last().unwrap()).collect::<String>()
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE_1: &str = include_str!("example_1.in");
     const EXAMPLE_2: &str = include_str!("example_2.in");
@@ -10,7 +16,17 @@ mod tests {
     const EXAMPLE_4: &str = include_str!("example_4.in");
     const EXAMPLE_5: &str = include_str!("example_5.in");
 
-    const INPUT: &str = include_str!("input.in");
+    #[test]
+    fn it_reports_malformed_input() {
+        assert_eq!(
+            solve_part1("mjqj pqm\n"),
+            Err(SolveError::Parse {
+                line: 1,
+                column: 5,
+                message: "expected a letter".to_string()
+            })
+        );
+    }
 
     mod part1 {
         use super::*;
@@ -18,37 +34,40 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 5);
+            assert_eq!(result, Ok(5));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part1(EXAMPLE_2);
-            assert_eq!(result, 6);
+            assert_eq!(result, Ok(6));
         }
 
         #[test]
         fn it_solves_example_3() {
             let result = solve_part1(EXAMPLE_3);
-            assert_eq!(result, 10);
+            assert_eq!(result, Ok(10));
         }
 
         #[test]
         fn it_solves_example_4() {
             let result = solve_part1(EXAMPLE_4);
-            assert_eq!(result, 11);
+            assert_eq!(result, Ok(11));
         }
 
         #[test]
         fn it_solves_example_5() {
             let result = solve_part1(EXAMPLE_5);
-            assert_eq!(result, 7);
+            assert_eq!(result, Ok(7));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 1238);
+            let input = test_input(2022, 6);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(1238));
         }
     }
 
@@ -58,53 +77,65 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part2(EXAMPLE_1);
-            assert_eq!(result, 23);
+            assert_eq!(result, Ok(23));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part2(EXAMPLE_2);
-            assert_eq!(result, 23);
+            assert_eq!(result, Ok(23));
         }
 
         #[test]
         fn it_solves_example_3() {
             let result = solve_part2(EXAMPLE_3);
-            assert_eq!(result, 29);
+            assert_eq!(result, Ok(29));
         }
 
         #[test]
         fn it_solves_example_4() {
             let result = solve_part2(EXAMPLE_4);
-            assert_eq!(result, 26);
+            assert_eq!(result, Ok(26));
         }
 
         #[test]
         fn it_solves_example_5() {
             let result = solve_part2(EXAMPLE_5);
-            assert_eq!(result, 19);
+            assert_eq!(result, Ok(19));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 3037);
+            let input = test_input(2022, 6);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(3037));
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
     solve_n(input, 4)
 }
 
-pub fn solve_part2(input: &str) -> i32 {
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
     solve_n(input, 14)
 }
 
-fn solve_n(input: &str, n: usize) -> i32 {
+fn parse_datastream(input: &str) -> Result<Vec<char>, SolveError> {
+    let stream = input.trim_end();
+
+    match stream.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(i) => Err(SolveError::parse(input, &stream[i..], "expected a letter")),
+        None => Ok(stream.chars().collect()),
+    }
+}
+
+fn solve_n(input: &str, n: usize) -> Result<i32, SolveError> {
     let mut char_counts = HashMap::<char, usize>::new();
 
-    let chars = input.chars().collect::<Vec<_>>();
+    let chars = parse_datastream(input)?;
 
     for (i, c) in chars.iter().enumerate() {
         *char_counts.entry(*c).or_insert(0) += 1;
@@ -117,9 +148,27 @@ fn solve_n(input: &str, n: usize) -> i32 {
         }
 
         if char_counts.keys().count() == n {
-            return (i + 1) as i32;
+            return Ok((i + 1) as i32);
         }
     }
 
-    -1
+    Err(SolveError::no_solution(format!(
+        "no marker of {n} distinct characters"
+    )))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_datastream(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,11 +1,16 @@
 use std::collections::{BTreeMap, HashMap};
 
+use crate::{
+    error::{parse_number, SolveError},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
 
     mod part1 {
         use super::*;
@@ -13,13 +18,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 95437);
+            assert_eq!(result, Ok(95437));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 1581595);
+            let input = test_input(2022, 7);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(1581595));
         }
     }
 
@@ -29,13 +37,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 24933642);
+            assert_eq!(result, Ok(24933642));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 1544176);
+            let input = test_input(2022, 7);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(1544176));
         }
     }
 }
@@ -68,29 +79,44 @@ enum TerminalEntry {
     Output(LsOutput),
 }
 
-fn parse_terminal_output(line: &str) -> TerminalEntry {
-    match line.split_once(' ') {
+fn parse_terminal_output(input: &str, line: &str) -> Result<TerminalEntry, SolveError> {
+    Ok(match line.split_once(' ') {
         Some(("$", "ls")) => TerminalEntry::Command(Command::Ls),
         Some(("$", rest)) => match rest.split_once(' ') {
             Some(("cd", "/")) => TerminalEntry::Command(Command::CdRoot),
             Some(("cd", "..")) => TerminalEntry::Command(Command::CdUp),
             Some(("cd", dir_name)) => TerminalEntry::Command(Command::CdDown(dir_name.to_string())),
             None => TerminalEntry::Command(Command::Ls),
-            _ => panic!(),
+            _ => return Err(SolveError::parse(input, rest, "unknown command")),
         },
         Some(("dir", dir_name)) => TerminalEntry::Output(LsOutput::Dir(dir_name.to_string())),
-        Some((size, file_name)) => {
-            TerminalEntry::Output(LsOutput::File(file_name.to_string(), size.parse().unwrap()))
-        }
-        _ => panic!(),
-    }
+        Some((size, file_name)) => TerminalEntry::Output(LsOutput::File(
+            file_name.to_string(),
+            parse_number(input, size)?,
+        )),
+        _ => return Err(SolveError::parse(input, line, "unexpected line")),
+    })
+}
+
+fn parse_terminal_outputs(input: &str) -> Result<Vec<TerminalEntry>, SolveError> {
+    input
+        .lines()
+        .map(|line| parse_terminal_output(input, line))
+        .collect()
 }
 
-fn parse_terminal_outputs(input: &str) -> Vec<TerminalEntry> {
-    input.lines().map(parse_terminal_output).collect()
+fn get_dir<'a>(root: &'a mut DirEntry, context: &[String]) -> Result<&'a mut DirEntry, SolveError> {
+    let mut curr = root;
+    for dir in context.iter() {
+        curr = curr
+            .dirs
+            .get_mut(dir)
+            .ok_or_else(|| SolveError::no_solution(format!("unknown directory {dir}")))?;
+    }
+    Ok(curr)
 }
 
-fn parse_fs(input: &str) -> DirEntry {
+fn parse_fs(input: &str) -> Result<DirEntry, SolveError> {
     let mut root = DirEntry {
         name: "/".to_string(),
         dirs: HashMap::new(),
@@ -99,7 +125,7 @@ fn parse_fs(input: &str) -> DirEntry {
 
     let mut context: Vec<String> = vec![];
 
-    for line in parse_terminal_outputs(input).into_iter() {
+    for line in parse_terminal_outputs(input)?.into_iter() {
         match line {
             TerminalEntry::Command(Command::CdRoot) => {
                 context = vec![];
@@ -112,10 +138,7 @@ fn parse_fs(input: &str) -> DirEntry {
             }
             TerminalEntry::Command(Command::Ls) => { /* do nothing really */ }
             TerminalEntry::Output(LsOutput::Dir(dir_name)) => {
-                let mut curr = &mut root;
-                for dir in context.iter() {
-                    curr = curr.dirs.get_mut(dir).unwrap();
-                }
+                let curr = get_dir(&mut root, &context)?;
                 curr.dirs.insert(
                     dir_name.to_string(),
                     DirEntry {
@@ -126,21 +149,17 @@ fn parse_fs(input: &str) -> DirEntry {
                 );
             }
             TerminalEntry::Output(LsOutput::File(name, size)) => {
-                let mut curr = &mut root;
-                for dir in context.iter() {
-                    curr = curr.dirs.get_mut(dir).unwrap();
-                }
-                curr.files
-                    .insert(name.to_string(), FileEntry { size });
+                let curr = get_dir(&mut root, &context)?;
+                curr.files.insert(name.to_string(), FileEntry { size });
             }
         };
     }
 
-    root
+    Ok(root)
 }
 
-pub fn solve_part1(input: &str) -> usize {
-    let root = parse_fs(input);
+pub fn solve_part1(input: &str) -> Result<usize, SolveError> {
+    let root = parse_fs(input)?;
 
     let mut dir_sizes: BTreeMap<String, usize> = BTreeMap::new();
 
@@ -156,10 +175,10 @@ pub fn solve_part1(input: &str) -> usize {
         );
     });
 
-    dir_sizes
+    Ok(dir_sizes
         .values()
         .filter(|size| **size < 100_000)
-        .sum::<usize>()
+        .sum::<usize>())
 }
 
 fn depth_first(root: &DirEntry, f: &mut impl FnMut(&DirEntry, &str)) {
@@ -173,8 +192,8 @@ fn depth_first_helper(root: &DirEntry, path: &str, f: &mut impl FnMut(&DirEntry,
     f(root, path);
 }
 
-pub fn solve_part2(input: &str) -> usize {
-    let root = parse_fs(input);
+pub fn solve_part2(input: &str) -> Result<usize, SolveError> {
+    let root = parse_fs(input)?;
     let mut dir_sizes: BTreeMap<String, usize> = BTreeMap::new();
     depth_first(&root, &mut |dir: &DirEntry, path: &str| {
         dir_sizes.insert(
@@ -187,12 +206,28 @@ pub fn solve_part2(input: &str) -> usize {
                     .sum::<usize>(),
         );
     });
-    let missing_space = dir_sizes[""] - 40_000_000;
+    let missing_space = dir_sizes[""].saturating_sub(40_000_000);
     let mut sorted_sizes = dir_sizes.values().collect::<Vec<_>>();
     sorted_sizes.sort();
     sorted_sizes
         .into_iter()
         .find(|size| **size >= missing_space)
-        .unwrap()
-        .to_owned()
+        .copied()
+        .ok_or_else(|| SolveError::no_solution("no directory is large enough"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_fs(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,11 +1,30 @@
 use std::collections::HashSet;
 
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE: &str = include_str!("example.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_reports_ragged_rows() {
+        assert_eq!(
+            solve_part1("123\n45\n678\n"),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 1,
+                message: "rows must have the same width".to_string()
+            })
+        );
+        assert_eq!(solve_part1("30373\n25512\n\n"), Ok(10));
+    }
 
     mod part1 {
         use super::*;
@@ -13,13 +32,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 21);
+            assert_eq!(result, Ok(21));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 1851);
+            let input = test_input(2022, 8);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(1851));
         }
     }
 
@@ -29,30 +51,28 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 8);
+            assert_eq!(result, Ok(8));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 574080);
+            let input = test_input(2022, 8);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(574080));
         }
     }
 }
 
-fn parse_trees(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect()
-        })
-        .collect()
+fn parse_trees(input: &str) -> Result<Vec<Vec<i32>>, SolveError> {
+    let trees = Grid::parse(input, |c| c.to_digit(10).map(|d| d as i32))?;
+
+    Ok(trees.rows().map(<[i32]>::to_vec).collect())
 }
 
-pub fn solve_part1(input: &str) -> i32 {
-    let mat = parse_trees(input);
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
+    let mat = parse_trees(input)?;
 
     let mut counted = HashSet::new();
 
@@ -107,11 +127,11 @@ pub fn solve_part1(input: &str) -> i32 {
         }
     }
 
-    counted.len() as i32
+    Ok(counted.len() as i32)
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    let mat = parse_trees(input);
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
+    let mat = parse_trees(input)?;
 
     let mut max_scenic_score = 0;
 
@@ -159,5 +179,21 @@ pub fn solve_part2(input: &str) -> i32 {
         }
     }
 
-    max_scenic_score
+    Ok(max_scenic_score)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_trees(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
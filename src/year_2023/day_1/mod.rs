@@ -1,10 +1,27 @@
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::test_input;
 
     const EXAMPLE_1: &str = include_str!("example_1.in");
     const EXAMPLE_2: &str = include_str!("example_2.in");
-    const INPUT: &str = include_str!("input.in");
+
+    #[test]
+    fn it_reports_malformed_input() {
+        assert_eq!(
+            solve_part2("two1nine\neight-wo3three"),
+            Err(SolveError::Parse {
+                line: 2,
+                column: 6,
+                message: "expected letters and digits".to_string()
+            })
+        );
+    }
 
     mod part1 {
         use super::*;
@@ -12,13 +29,16 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 142);
+            assert_eq!(result, Ok(142));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part1(INPUT);
-            assert_eq!(result, 54968);
+            let input = test_input(2023, 1);
+
+            let result = solve_part1(&input);
+            assert_eq!(result, Ok(54968));
         }
     }
 
@@ -28,54 +48,72 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE_2);
-            assert_eq!(result, 281);
+            assert_eq!(result, Ok(281));
         }
 
         #[test]
+        #[ignore]
         fn it_solves_input() {
-            let result = solve_part2(INPUT);
-            assert_eq!(result, 54094);
+            let input = test_input(2023, 1);
+
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(54094));
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> u32 {
+fn missing_digit(input: &str, row: &str) -> SolveError {
+    SolveError::parse(input, row, "no digit in line")
+}
+
+// Both parts need a digit on every line, part 2 just reads more of them
+fn parse_rows(input: &str) -> Result<Vec<&str>, SolveError> {
+    input
+        .lines()
+        .map(|row| match row.find(|c: char| !c.is_ascii_alphanumeric()) {
+            Some(i) => Err(SolveError::parse(
+                input,
+                &row[i..],
+                "expected letters and digits",
+            )),
+            None => Ok(row),
+        })
+        .collect()
+}
+
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
     let mut sum = 0;
 
-    for row in input.lines() {
+    for row in parse_rows(input)? {
         let first_digit = row
             .chars()
-            .find(|c| c.is_numeric())
-            .map(|c| c.to_digit(10))
-            .unwrap()
-            .unwrap();
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| missing_digit(input, row))?;
         let last_digit = row
             .chars()
             .rev()
-            .find(|c| c.is_numeric())
-            .map(|c| c.to_digit(10))
-            .unwrap()
-            .unwrap();
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| missing_digit(input, row))?;
 
         sum += first_digit * 10 + last_digit;
     }
 
-    sum
+    Ok(sum)
 }
 
 const DIGIT_WORDS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-pub fn solve_part2(input: &str) -> u32 {
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
     let mut sum = 0;
 
-    for row in input.lines() {
+    for row in parse_rows(input)? {
         let first_digit = row
             .char_indices()
             .find_map(|(i, c)| {
-                if c.is_numeric() {
-                    Some(c.to_digit(10).unwrap())
+                if let Some(d) = c.to_digit(10) {
+                    Some(d)
                 } else {
                     DIGIT_WORDS
                         .iter()
@@ -89,14 +127,14 @@ pub fn solve_part2(input: &str) -> u32 {
                         })
                 }
             })
-            .unwrap();
+            .ok_or_else(|| missing_digit(input, row))?;
 
         let last_digit = row
             .char_indices()
             .rev()
             .find_map(|(i, c)| {
-                if c.is_numeric() {
-                    Some(c.to_digit(10).unwrap())
+                if let Some(d) = c.to_digit(10) {
+                    Some(d)
                 } else {
                     DIGIT_WORDS
                         .iter()
@@ -110,9 +148,25 @@ pub fn solve_part2(input: &str) -> u32 {
                         })
                 }
             })
-            .unwrap();
+            .ok_or_else(|| missing_digit(input, row))?;
         sum += first_digit * 10 + last_digit;
     }
 
-    sum
+    Ok(sum)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_rows(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -2,6 +2,12 @@ use std::collections::{HashSet, VecDeque};
 
 use num::Integer;
 
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,13 +25,13 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 4);
+            assert_eq!(result, Ok(4));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part1(EXAMPLE_2);
-            assert_eq!(result, 8);
+            assert_eq!(result, Ok(8));
         }
     }
 
@@ -35,54 +41,49 @@ mod tests {
         #[test]
         fn it_solves_example_3() {
             let result = solve_part2(EXAMPLE_3);
-            assert_eq!(result, 4);
+            assert_eq!(result, Ok(4));
         }
 
         #[test]
         fn it_solves_example_4() {
             let result = solve_part2(EXAMPLE_4);
-            assert_eq!(result, 4);
+            assert_eq!(result, Ok(4));
         }
 
         #[test]
         fn it_solves_example_5() {
             let result = solve_part2(EXAMPLE_5);
-            assert_eq!(result, 8);
+            assert_eq!(result, Ok(8));
         }
 
         #[test]
         fn it_solves_example_6() {
             let result = solve_part2(EXAMPLE_6);
-            assert_eq!(result, 10);
+            assert_eq!(result, Ok(10));
         }
     }
 }
 
-fn parse(input: &str) -> (Vec<Vec<char>>, (usize, usize)) {
-    let map = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+type Map = Grid<char>;
+
+fn parse(input: &str) -> Result<(Map, (usize, usize)), SolveError> {
+    let map = Grid::parse(input, |c| {
+        matches!(c, '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' | 'S').then_some(c)
+    })?;
 
     let start = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .find_map(|(x, &c)| if c == 'S' { Some((x, y)) } else { None })
-        })
-        .unwrap();
-
-    (map, start)
+        .position(|&c| c == 'S')
+        .ok_or_else(|| SolveError::no_solution("no starting position"))?;
+
+    Ok((map, start))
 }
 
-fn neighbors(map: &[Vec<char>], &(x, y): &(usize, usize)) -> Vec<(usize, usize)> {
+fn neighbors(map: &Map, &(x, y): &(usize, usize)) -> Vec<(usize, usize)> {
     let mut neighbors = Vec::new();
 
     // North
-    if y > 0
-        && match (map[y][x], map[y - 1][x]) {
+    if let Some(north) = map.offset((x, y), (0, -1)) {
+        if match (map[(x, y)], map[north]) {
             (_, '.') => false,
             (_, 'S') => true,
             ('-', _) => false,
@@ -92,14 +93,14 @@ fn neighbors(map: &[Vec<char>], &(x, y): &(usize, usize)) -> Vec<(usize, usize)>
             (_, '7') => true,
             (_, 'F') => true,
             _ => false,
+        } {
+            neighbors.push(north);
         }
-    {
-        neighbors.push((x, y - 1));
     }
 
     // East
-    if let Some(neighbor) = map.get(y).and_then(|row| row.get(x + 1)) {
-        if match (map[y][x], neighbor) {
+    if let Some(east) = map.offset((x, y), (1, 0)) {
+        if match (map[(x, y)], map[east]) {
             (_, '.') => false,
             (_, 'S') => true,
             ('|', _) => false,
@@ -110,13 +111,13 @@ fn neighbors(map: &[Vec<char>], &(x, y): &(usize, usize)) -> Vec<(usize, usize)>
             (_, 'J') => true,
             _ => false,
         } {
-            neighbors.push((x + 1, y));
+            neighbors.push(east);
         }
     }
 
     // South
-    if let Some(neighbor) = map.get(y + 1).and_then(|row| row.get(x)) {
-        if match (map[y][x], neighbor) {
+    if let Some(south) = map.offset((x, y), (0, 1)) {
+        if match (map[(x, y)], map[south]) {
             (_, '.') => false,
             (_, 'S') => true,
             ('-', _) => false,
@@ -127,13 +128,13 @@ fn neighbors(map: &[Vec<char>], &(x, y): &(usize, usize)) -> Vec<(usize, usize)>
             (_, 'J') => true,
             _ => false,
         } {
-            neighbors.push((x, y + 1));
+            neighbors.push(south);
         }
     }
 
     // West
-    if x > 0
-        && match (map[y][x], map[y][x - 1]) {
+    if let Some(west) = map.offset((x, y), (-1, 0)) {
+        if match (map[(x, y)], map[west]) {
             (_, '.') => false,
             (_, 'S') => true,
             ('|', _) => false,
@@ -143,20 +144,20 @@ fn neighbors(map: &[Vec<char>], &(x, y): &(usize, usize)) -> Vec<(usize, usize)>
             (_, 'L') => true,
             (_, 'F') => true,
             _ => false,
+        } {
+            neighbors.push(west);
         }
-    {
-        neighbors.push((x - 1, y));
     }
 
     neighbors
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (map, start) = parse(input);
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let (map, start) = parse(input)?;
 
     let mut visited = HashSet::new();
     let mut queue = VecDeque::from([start]);
-    let mut level = 0;
+    let mut level: u32 = 0;
 
     while !queue.is_empty() {
         for _ in 0..queue.len() {
@@ -173,11 +174,14 @@ pub fn solve_part1(input: &str) -> u32 {
         level += 1;
     }
 
-    level - 2
+    level
+        .checked_sub(2)
+        .filter(|&distance| distance > 0)
+        .ok_or_else(|| SolveError::no_solution("the start is not on a loop"))
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (map, start) = parse(input);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let (map, start) = parse(input)?;
 
     let mut visited = HashSet::new();
     let mut queue = VecDeque::from([start]);
@@ -195,12 +199,12 @@ pub fn solve_part2(input: &str) -> u32 {
 
     let mut count = 0;
 
-    for (y, _) in map.iter().enumerate() {
+    for (y, row) in map.rows().enumerate() {
         let mut intersects = 0;
 
-        for (x, _) in map[y].iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
             if visited.contains(&(x, y)) {
-                if matches!(map[y][x], 'J' | 'L' | '|') {
+                if matches!(tile, 'J' | 'L' | '|') {
                     intersects += 1;
                 }
             } else if intersects.is_odd() {
@@ -209,5 +213,21 @@ pub fn solve_part2(input: &str) -> u32 {
         }
     }
 
-    count
+    Ok(count)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,6 +1,11 @@
 use itertools::Itertools;
 use std::collections::HashSet;
 
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,7 +18,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 374);
+            assert_eq!(result, Ok(374));
         }
     }
 
@@ -23,29 +28,39 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part2(EXAMPLE, 10);
-            assert_eq!(result, 1030);
+            assert_eq!(result, Ok(1030));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part2(EXAMPLE, 100);
-            assert_eq!(result, 8410);
+            assert_eq!(result, Ok(8410));
         }
     }
 }
 
 type Map = HashSet<(usize, usize)>;
 
-fn parse_map(input: &str) -> Map {
+fn parse_map(input: &str) -> Result<Map, SolveError> {
     let mut map = Map::new();
+
     for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                map.insert((x, y));
+        for (x, c) in line.char_indices() {
+            match c {
+                '#' => {
+                    map.insert((x, y));
+                }
+                '.' => {}
+                _ => return Err(SolveError::parse(input, &line[x..], "expected '#' or '.'")),
             }
         }
     }
-    map
+
+    if map.is_empty() {
+        return Err(SolveError::no_solution("no galaxies in the image"));
+    }
+
+    Ok(map)
 }
 
 fn expand_cosmos(map: &mut Map, age: &usize) {
@@ -94,13 +109,29 @@ fn sum_shortest_paths(map: &Map) -> u64 {
     map.iter().tuple_combinations().map(distance).sum()
 }
 
-pub fn solve_part1(input: &str) -> u64 {
-    let mut map = parse_map(input);
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    let mut map = parse_map(input)?;
     expand_cosmos(&mut map, &2);
-    sum_shortest_paths(&map)
+    Ok(sum_shortest_paths(&map))
 }
-pub fn solve_part2(input: &str, age: usize) -> u64 {
-    let mut map = parse_map(input);
+pub fn solve_part2(input: &str, age: usize) -> Result<u64, SolveError> {
+    let mut map = parse_map(input)?;
     expand_cosmos(&mut map, &age);
-    sum_shortest_paths(&map)
+    Ok(sum_shortest_paths(&map))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_map(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input, 1_000_000).map(Answer::from))
+    }
 }
@@ -1,15 +1,20 @@
-use std::collections::HashMap;
-
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{newline, u32},
+    character::complete::u32,
     combinator::map,
     multi::{many1, separated_list1},
     sequence::separated_pair,
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    memo::Memo,
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,7 +27,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 21);
+            assert_eq!(result, Ok(21));
         }
     }
 
@@ -32,7 +37,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 525152);
+            assert_eq!(result, Ok(525152));
         }
     }
 }
@@ -54,56 +59,48 @@ fn parse_status(input: &str) -> IResult<&str, Status> {
     ))(input)
 }
 fn parse(input: &str) -> IResult<&str, Vec<Record>> {
-    separated_list1(
-        newline,
-        separated_pair(
-            many1(parse_status),
-            tag(" "),
-            separated_list1(tag(","), u32),
-        ),
-    )(input)
+    lines(separated_pair(
+        many1(parse_status),
+        tag(" "),
+        separated_list1(tag(","), u32),
+    ))(input)
 }
 
-type CacheKey = (usize, usize, Option<u32>);
+// Keyed by how much of the record is left, which is all that varies between calls
+type ArrangementsMemo = Memo<(usize, usize, Option<u32>), u64>;
 
 fn count_arrangements(
     statuses: &[Status],
     groups: &[u32],
     consecutive: Option<u32>,
-    cache: &mut HashMap<CacheKey, u64>,
+    memo: &mut ArrangementsMemo,
 ) -> u64 {
     if let Some(curr) = statuses.first() {
         use Status::*;
 
         let key = (statuses.len(), groups.len(), consecutive);
 
-        if let Some(value) = cache.get(&key) {
-            return *value;
-        }
-
-        let value = match (curr, &consecutive, groups.first()) {
+        memo.get_or_insert_with(key, |memo| match (curr, &consecutive, groups.first()) {
             (_, Some(_), None) => 0,
             (Operational, Some(consecutive), Some(group)) if consecutive != group => 0,
-            (Operational, None, _) => count_arrangements(&statuses[1..], groups, None, cache),
+            (Operational, None, _) => count_arrangements(&statuses[1..], groups, None, memo),
             (Damaged, _, None) => 0,
             (Damaged, Some(consecutive), Some(group)) if consecutive >= group => 0,
             (Damaged, Some(consecutive), Some(_)) => {
-                count_arrangements(&statuses[1..], groups, Some(consecutive + 1), cache)
+                count_arrangements(&statuses[1..], groups, Some(consecutive + 1), memo)
             }
-            (Damaged, None, _) => count_arrangements(&statuses[1..], groups, Some(1), cache),
-            (Unknown, None, None) => count_arrangements(&statuses[1..], groups, None, cache),
+            (Damaged, None, _) => count_arrangements(&statuses[1..], groups, Some(1), memo),
+            (Unknown, None, None) => count_arrangements(&statuses[1..], groups, None, memo),
             (Unknown, None, Some(_)) => {
-                count_arrangements(&statuses[1..], groups, None, cache)
-                    + count_arrangements(&statuses[1..], groups, Some(1), cache)
+                count_arrangements(&statuses[1..], groups, None, memo)
+                    + count_arrangements(&statuses[1..], groups, Some(1), memo)
             }
             (Unknown, Some(consecutive), Some(group)) if consecutive > group => 0,
             (Unknown, Some(consecutive), Some(group)) if consecutive < group => {
-                count_arrangements(&statuses[1..], groups, Some(consecutive + 1), cache)
+                count_arrangements(&statuses[1..], groups, Some(consecutive + 1), memo)
             }
-            (_, Some(_), Some(_)) => count_arrangements(&statuses[1..], &groups[1..], None, cache),
-        };
-        cache.insert(key, value);
-        value
+            (_, Some(_), Some(_)) => count_arrangements(&statuses[1..], &groups[1..], None, memo),
+        })
     } else {
         match (consecutive, groups.len(), groups.first()) {
             (None, 0, _) => 1,
@@ -145,16 +142,38 @@ fn unfold((statuses, groups): &Record) -> Record {
 }
 fn solve(rows: &[Record]) -> u64 {
     rows.iter()
-        .map(|(statuses, groups)| count_arrangements(statuses, groups, None, &mut HashMap::new()))
+        .map(|(statuses, groups)| {
+            let mut memo = Memo::new();
+            let arrangements = count_arrangements(statuses, groups, None, &mut memo);
+            memo.trace_stats("arrangements");
+            arrangements
+        })
         .sum()
 }
-pub fn solve_part1(input: &str) -> u64 {
-    let (_, rows) = parse(input).unwrap();
-    solve(&rows)
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    let rows = parse_all(input, parse)?;
+
+    Ok(solve(&rows))
 }
-pub fn solve_part2(input: &str) -> u64 {
-    let (_, rows) = parse(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let rows = parse_all(input, parse)?;
     let rows = rows.iter().map(unfold).collect::<Vec<_>>();
 
-    solve(&rows)
+    Ok(solve(&rows))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,10 +1,8 @@
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::newline,
-    combinator::map,
-    multi::{many1, separated_list1},
-    IResult,
+use crate::{
+    error::{parse_all, SolveError},
+    grid::Grid,
+    parsers::{blocks, char_grid},
+    solution::{Answer, Solution},
 };
 
 #[cfg(test)]
@@ -19,7 +17,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 405);
+            assert_eq!(result, Ok(405));
         }
     }
 
@@ -29,7 +27,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 400);
+            assert_eq!(result, Ok(400));
         }
     }
 }
@@ -45,54 +43,66 @@ fn reflection(nums: &[u32], xored_ones: u32) -> Option<usize> {
             == xored_ones
     })
 }
+fn bitmasks(map: &Map) -> Vec<u32> {
+    map.rows()
+        .map(|row| row.iter().fold(0, |acc, cell| (acc << 1) | *cell as u32))
+        .collect()
+}
 fn horizontal_reflection(map: &Map, xored_ones: u32) -> Option<usize> {
-    let mut cols = vec![0; map[0].len()];
-
-    for row in map {
-        for (col, cell) in row.iter().enumerate() {
-            cols[col] <<= 1;
-            cols[col] |= *cell as u32;
-        }
-    }
-
-    reflection(&cols, xored_ones)
+    reflection(&bitmasks(&map.transpose()), xored_ones)
 }
 fn vertical_reflection(map: &Map, xored_ones: u32) -> Option<usize> {
-    let rows = map
-        .iter()
-        .map(|row| row.iter().fold(0, |acc, cell| (acc << 1) | *cell as u32))
-        .collect::<Vec<_>>();
-
-    reflection(&rows, xored_ones)
+    reflection(&bitmasks(map), xored_ones)
 }
 
-type Map = Vec<Vec<bool>>;
+type Map = Grid<bool>;
 
-fn parse_map_row(input: &str) -> IResult<&str, Vec<bool>> {
-    many1(alt((map(tag("."), |_| false), map(tag("#"), |_| true))))(input)
-}
-fn parse_map(input: &str) -> IResult<&str, Map> {
-    separated_list1(newline, parse_map_row)(input)
-}
-fn parse_maps(input: &str) -> IResult<&str, Vec<Map>> {
-    separated_list1(tag("\n\n"), parse_map)(input)
+fn parse_maps(input: &str) -> Result<Vec<Map>, SolveError> {
+    parse_all(
+        input,
+        blocks(char_grid(|c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })),
+    )
 }
 
-fn solve(input: &str, xored_ones: u32) -> u32 {
-    let (_, maps) = parse_maps(input).unwrap();
+fn solve(input: &str, xored_ones: u32) -> Result<u32, SolveError> {
+    let maps = parse_maps(input)?;
 
     maps.iter()
-        .map(|map| {
+        .enumerate()
+        .map(|(i, map)| {
             horizontal_reflection(map, xored_ones)
                 .map(|col| col as u32)
                 .or(vertical_reflection(map, xored_ones).map(|row| row as u32 * 100))
-                .unwrap()
+                .ok_or_else(|| {
+                    SolveError::no_solution(format!("no reflection in pattern {}", i + 1))
+                })
         })
-        .sum::<u32>()
+        .sum::<Result<u32, _>>()
 }
-pub fn solve_part1(input: &str) -> u32 {
+
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
     solve(input, 0)
 }
-pub fn solve_part2(input: &str) -> u32 {
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
     solve(input, 1)
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_maps(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
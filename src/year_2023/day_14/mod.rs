@@ -1,14 +1,21 @@
-use std::collections::HashMap;
-
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::newline,
-    combinator::map,
-    multi::{many1, separated_list1},
+    combinator::{map, verify},
+    multi::many1,
     IResult,
 };
 
+use crate::{
+    cycles::state_after,
+    error::{parse_all, SolveError},
+    grid::Grid,
+    parsers::lines,
+    solution::{Answer, Solution},
+    trace_event,
+    visualize::{self, Frame},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,7 +28,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 136);
+            assert_eq!(result, Ok(136));
         }
     }
 
@@ -31,7 +38,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 64);
+            assert_eq!(result, Ok(64));
         }
     }
 }
@@ -51,10 +58,30 @@ fn parse_map_row(input: &str) -> IResult<&str, Vec<Cell>> {
     )))(input)
 }
 fn parse_map(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    separated_list1(newline, parse_map_row)(input)
+    verify(lines(parse_map_row), |map: &[Vec<Cell>]| {
+        map.iter().all(|row| row.len() == map[0].len())
+    })(input)
 }
 
-fn tilt_north(map: &mut Vec<Vec<Cell>>) {
+fn draw(map: &[Vec<Cell>]) -> Frame {
+    let rows = map
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Cell::Empty => '.',
+                    Cell::RoundRock => 'O',
+                    Cell::CubeRock => '#',
+                })
+                .collect()
+        })
+        .collect();
+
+    Frame::new(Grid::from_rows(rows).unwrap())
+}
+
+#[allow(clippy::needless_range_loop)]
+fn tilt_north(map: &mut [Vec<Cell>]) {
     let width = map[0].len();
     let height = map.len();
     let mut moved = true;
@@ -68,14 +95,18 @@ fn tilt_north(map: &mut Vec<Vec<Cell>>) {
                     if let Cell::Empty = map[y - 1][x] {
                         map[y][x] = Cell::Empty;
                         map[y - 1][x] = Cell::RoundRock;
+                        trace_event!("rock moved", from = (x, y), to = (x, y - 1));
                         moved = true;
                     }
                 }
             }
         }
+
+        visualize::frame(|| draw(map));
     }
 }
-fn tilt_west(map: &mut Vec<Vec<Cell>>) {
+#[allow(clippy::needless_range_loop)]
+fn tilt_west(map: &mut [Vec<Cell>]) {
     let width = map[0].len();
     let height = map.len();
     let mut moved = true;
@@ -89,14 +120,18 @@ fn tilt_west(map: &mut Vec<Vec<Cell>>) {
                     if let Cell::Empty = map[y][x - 1] {
                         map[y][x] = Cell::Empty;
                         map[y][x - 1] = Cell::RoundRock;
+                        trace_event!("rock moved", from = (x, y), to = (x - 1, y));
                         moved = true;
                     }
                 }
             }
         }
+
+        visualize::frame(|| draw(map));
     }
 }
-fn tilt_south(map: &mut Vec<Vec<Cell>>) {
+#[allow(clippy::needless_range_loop)]
+fn tilt_south(map: &mut [Vec<Cell>]) {
     let width = map[0].len();
     let height = map.len();
     let mut moved = true;
@@ -110,14 +145,18 @@ fn tilt_south(map: &mut Vec<Vec<Cell>>) {
                     if let Cell::Empty = map[y + 1][x] {
                         map[y][x] = Cell::Empty;
                         map[y + 1][x] = Cell::RoundRock;
+                        trace_event!("rock moved", from = (x, y), to = (x, y + 1));
                         moved = true;
                     }
                 }
             }
         }
+
+        visualize::frame(|| draw(map));
     }
 }
-fn tilt_east(map: &mut Vec<Vec<Cell>>) {
+#[allow(clippy::needless_range_loop)]
+fn tilt_east(map: &mut [Vec<Cell>]) {
     let width = map[0].len();
     let height = map.len();
     let mut moved = true;
@@ -131,22 +170,25 @@ fn tilt_east(map: &mut Vec<Vec<Cell>>) {
                     if let Cell::Empty = map[y][x + 1] {
                         map[y][x] = Cell::Empty;
                         map[y][x + 1] = Cell::RoundRock;
+                        trace_event!("rock moved", from = (x, y), to = (x + 1, y));
                         moved = true;
                     }
                 }
             }
         }
+
+        visualize::frame(|| draw(map));
     }
 }
 
-fn spin_cycle(map: &mut Vec<Vec<Cell>>) {
+fn spin_cycle(map: &mut [Vec<Cell>]) {
     tilt_north(map);
     tilt_west(map);
     tilt_south(map);
     tilt_east(map);
 }
 
-fn total_north_beam_load(map: &Vec<Vec<Cell>>) -> u32 {
+fn total_north_beam_load(map: &[Vec<Cell>]) -> u32 {
     map.iter()
         .enumerate()
         .map(|(y, row)| {
@@ -155,35 +197,38 @@ fn total_north_beam_load(map: &Vec<Vec<Cell>>) -> u32 {
         .sum()
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, mut map) = parse_map(input).unwrap();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let mut map = parse_all(input, parse_map)?;
     tilt_north(&mut map);
-    total_north_beam_load(&map)
+    Ok(total_north_beam_load(&map))
 }
 
 const CYCLES: usize = 1000000000;
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (_, mut map) = parse_map(input).unwrap();
-
-    let mut seen_maps = HashMap::from([(map.clone(), 0)]);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let map = parse_all(input, parse_map)?;
 
-    for i in 0..CYCLES {
+    let map = state_after(map, CYCLES, |map| {
+        let mut map = map.clone();
         spin_cycle(&mut map);
+        map
+    });
 
-        if let Some(seen_i) = seen_maps.get(&map) {
-            let cycle_length = i - seen_i;
-            let remaining = CYCLES - i - 1;
-            let remaining_i = remaining % cycle_length;
+    Ok(total_north_beam_load(&map))
+}
 
-            for _ in 0..remaining_i {
-                spin_cycle(&mut map);
-            }
-            break;
-        }
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse_map).map(|_| ()))
+    }
 
-        seen_maps.insert(map.clone(), i);
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
     }
 
-    total_north_beam_load(&map)
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -7,6 +7,11 @@ use nom::{
     IResult,
 };
 
+use crate::{
+    error::SolveError,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,7 +24,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 1320);
+            assert_eq!(result, Ok(1320));
         }
     }
 
@@ -29,7 +34,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 145);
+            assert_eq!(result, Ok(145));
         }
     }
 }
@@ -48,8 +53,8 @@ fn hash(s: &str) -> u32 {
 
     h
 }
-pub fn solve_part1(input: &str) -> u32 {
-    parse_strings(input).iter().map(|s| hash(s)).sum()
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    Ok(parse_strings(input).iter().map(|s| hash(s)).sum())
 }
 
 #[derive(Debug)]
@@ -60,26 +65,37 @@ enum LensOp {
 
 type Step<'a> = (&'a str, LensOp);
 
-fn parse_remove_step(input: &str) -> IResult<&str, Step> {
+fn parse_remove_step(input: &str) -> IResult<&str, Step<'_>> {
     let (input, label) = take_until("-")(input)?;
     let (input, _) = tag("-")(input)?;
     Ok((input, (label, LensOp::Remove)))
 }
-fn parse_insert_step(input: &str) -> IResult<&str, Step> {
+fn parse_insert_step(input: &str) -> IResult<&str, Step<'_>> {
     let (input, label) = take_until("=")(input)?;
     let (input, _) = tag("=")(input)?;
     let (input, focal_length) = u8(input)?;
     Ok((input, (label, LensOp::Insert(focal_length))))
 }
-fn parse_step(input: &str) -> IResult<&str, Step> {
+fn parse_step(input: &str) -> IResult<&str, Step<'_>> {
     alt((parse_remove_step, parse_insert_step))(input)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let steps: Vec<(&str, LensOp)> = parse_strings(input)
+fn parse_steps(input: &str) -> Result<Vec<Step<'_>>, SolveError> {
+    parse_strings(input)
         .iter()
-        .map(|s| parse_step(s).unwrap().1)
-        .collect::<Vec<_>>();
+        .map(|s| match parse_step(s) {
+            Ok(("", step)) => Ok(step),
+            _ => Err(SolveError::parse(
+                input,
+                s,
+                "expected a remove or insert step",
+            )),
+        })
+        .collect()
+}
+
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let steps = parse_steps(input)?;
 
     let mut map = HashMap::new();
     let mut boxes: [Vec<(&str, u8)>; 256] = array::from_fn(|_| vec![]);
@@ -109,7 +125,7 @@ pub fn solve_part2(input: &str) -> u32 {
         }
     }
 
-    boxes
+    Ok(boxes
         .iter()
         .enumerate()
         .flat_map(|(box_i, b)| {
@@ -120,5 +136,21 @@ pub fn solve_part2(input: &str) -> u32 {
                 })
                 .collect::<Vec<_>>()
         })
-        .sum::<u32>()
+        .sum::<u32>())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_steps(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
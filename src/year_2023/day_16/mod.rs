@@ -2,6 +2,14 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use std::collections::{HashSet, VecDeque};
 
+use crate::{
+    error::SolveError,
+    geometry::Direction,
+    grid::Grid,
+    solution::{Answer, Solution},
+    visualize::{self, Frame},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,7 +22,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 46);
+            assert_eq!(result, Ok(46));
         }
     }
 
@@ -24,116 +32,119 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 51);
+            assert_eq!(result, Ok(51));
         }
     }
 }
 
-fn parse_map(input: &str) -> Vec<Vec<char>> {
-    input.trim().lines().map(|l| l.chars().collect()).collect()
+fn parse_map(input: &str) -> Result<Grid<char>, SolveError> {
+    Grid::parse_block(input, input.trim(), |c| {
+        matches!(c, '.' | '/' | '\\' | '|' | '-').then_some(c)
+    })
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+type Beam = (Direction, usize, usize);
+
+// Energized empty tiles light up, the rest keep their mirror or splitter
+fn draw(map: &Grid<char>, visited: &HashSet<Beam>) -> Frame {
+    let mut frame = map.clone();
+    for &(_, x, y) in visited {
+        if frame[(x, y)] == '.' {
+            frame[(x, y)] = '*';
+        }
+    }
 
-fn count_energized(map: &[Vec<char>], start: (Direction, usize, usize)) -> usize {
-    let height = map.len();
-    let width = map[0].len();
+    Frame::new(frame)
+}
 
+fn count_energized(map: &Grid<char>, start: Beam) -> usize {
     let mut visited = HashSet::new();
     let mut q = VecDeque::from([start]);
+    // How many more beams are as far from the start as the current one, a frame is drawn
+    // whenever the beams take another step
+    let mut wave = 0;
 
     while let Some(curr) = q.pop_front() {
+        if wave == 0 {
+            visualize::frame(|| draw(map, &visited));
+            wave = q.len();
+        } else {
+            wave -= 1;
+        }
+
         if !visited.insert(curr) {
             continue;
         }
 
         use Direction::*;
 
-        let curr = {
-            let (dir, x, y) = curr;
-            (dir, map[y][x], x, y)
+        let (dir, x, y) = curr;
+        let next: &[Direction] = match (dir, map[(x, y)]) {
+            (Up | Down, '|') | (Left | Right, '-') | (_, '.') => &[dir],
+            (Up | Down, '-') => &[Left, Right],
+            (Left | Right, '|') => &[Up, Down],
+            (Up, '/') | (Down, '\\') => &[Right],
+            (Down, '/') | (Up, '\\') => &[Left],
+            (Right, '/') | (Left, '\\') => &[Up],
+            (Left, '/') | (Right, '\\') => &[Down],
+            _ => unreachable!("tiles are validated while parsing"),
         };
 
-        match curr {
-            (Up, '.', _, 0) | (Up, '|', _, 0) => (),
-            (Up, '.', x, y) | (Up, '|', x, y) => q.push_back((Up, x, y - 1)),
-            (Up, '/', x, _) if x >= width - 1 => (),
-            (Up, '/', x, y) => q.push_back((Right, x + 1, y)),
-            (Up, '\\', 0, _) => (),
-            (Up, '\\', x, y) => q.push_back((Left, x - 1, y)),
-            (Up, '-', 0, y) => q.push_back((Right, 1, y)),
-            (Up, '-', x, y) if x == width - 1 => q.push_back((Left, x - 1, y)),
-            (Up, '-', x, y) => {
-                q.push_back((Left, x - 1, y));
-                q.push_back((Right, x + 1, y));
-            }
-            (Right, '.', x, _) | (Right, '-', x, _) if x >= width - 1 => (),
-            (Right, '.', x, y) | (Right, '-', x, y) => q.push_back((Right, x + 1, y)),
-            (Right, '/', _, 0) => (),
-            (Right, '/', x, y) => q.push_back((Up, x, y - 1)),
-            (Right, '\\', _, y) if y >= height - 1 => (),
-            (Right, '\\', x, y) => q.push_back((Down, x, y + 1)),
-            (Right, '|', x, 0) => q.push_back((Down, x, 1)),
-            (Right, '|', x, y) if y == height - 1 => q.push_back((Up, x, y - 1)),
-            (Right, '|', x, y) => {
-                q.push_back((Up, x, y - 1));
-                q.push_back((Down, x, y + 1));
-            }
-            (Down, '.', _, y) | (Down, '|', _, y) if y >= height - 1 => (),
-            (Down, '.', x, y) | (Down, '|', x, y) => q.push_back((Down, x, y + 1)),
-            (Down, '/', 0, _) => (),
-            (Down, '/', x, y) => q.push_back((Left, x - 1, y)),
-            (Down, '\\', x, _) if x >= width - 1 => (),
-            (Down, '\\', x, y) => q.push_back((Right, x + 1, y)),
-            (Down, '-', 0, y) => q.push_back((Right, 1, y)),
-            (Down, '-', x, y) if x == width - 1 => q.push_back((Left, x - 1, y)),
-            (Down, '-', x, y) => {
-                q.push_back((Left, x - 1, y));
-                q.push_back((Right, x + 1, y));
-            }
-            (Left, '.', 0, _) | (Left, '-', 0, _) => (),
-            (Left, '.', x, y) | (Left, '-', x, y) => q.push_back((Left, x - 1, y)),
-            (Left, '/', _, y) if y >= height - 1 => (),
-            (Left, '/', x, y) => q.push_back((Down, x, y + 1)),
-            (Left, '\\', _, 0) => (),
-            (Left, '\\', x, y) => q.push_back((Up, x, y - 1)),
-            (Left, '|', x, 0) => q.push_back((Down, x, 1)),
-            (Left, '|', x, y) if y == height - 1 => q.push_back((Up, x, y - 1)),
-            (Left, '|', x, y) => {
-                q.push_back((Up, x, y - 1));
-                q.push_back((Down, x, y + 1));
+        for &dir in next {
+            if let Some((x, y)) = map.offset((x, y), dir.delta()) {
+                q.push_back((dir, x, y));
             }
-            _ => unreachable!(),
         }
     }
 
     visited.iter().map(|(_, x, y)| (x, y)).unique().count()
 }
 
-pub fn solve_part1(input: &str) -> usize {
-    let map = parse_map(input);
-    count_energized(&map, (Direction::Right, 0usize, 0usize))
+pub fn solve_part1(input: &str) -> Result<usize, SolveError> {
+    let map = parse_map(input)?;
+
+    Ok(count_energized(&map, (Direction::Right, 0usize, 0usize)))
 }
 
-pub fn solve_part2(input: &str) -> usize {
-    let map = parse_map(input);
+pub fn solve_part2(input: &str) -> Result<usize, SolveError> {
+    let map = parse_map(input)?;
 
-    let height = map.len();
-    let width = map[0].len();
+    let height = map.height();
+    let width = map.width();
 
-    (0..width)
+    let starts = (0..width)
         .flat_map(|x| [(Direction::Down, x, 0), (Direction::Up, x, height - 1)])
         .chain(
             (0..height).flat_map(|y| [(Direction::Right, 0, y), (Direction::Left, width - 1, y)]),
-        )
+        );
+
+    // Frames drawn on the worker threads are dropped, so the best start is replayed here
+    if visualize::is_enabled() {
+        let best = starts
+            .par_bridge()
+            .max_by_key(|&start| count_energized(&map, start));
+        return Ok(best.map_or(0, |start| count_energized(&map, start)));
+    }
+
+    Ok(starts
         .par_bridge()
         .map(|start| count_energized(&map, start))
         .max()
-        .unwrap()
+        .unwrap_or(0))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_map(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
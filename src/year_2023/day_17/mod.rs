@@ -1,4 +1,10 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use crate::{
+    error::SolveError,
+    geometry::Direction,
+    grid::Grid,
+    search::dijkstra,
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -13,7 +19,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 102);
+            assert_eq!(result, Ok(102));
         }
     }
 
@@ -23,30 +29,19 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part2(EXAMPLE_1);
-            assert_eq!(result, 94);
+            assert_eq!(result, Ok(94));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part2(EXAMPLE_2);
-            assert_eq!(result, 71);
+            assert_eq!(result, Ok(71));
         }
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<u8>> {
-    input
-        .lines()
-        .map(|l| l.chars().map(|d| d.to_digit(10).unwrap() as u8).collect())
-        .collect()
-}
-
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+fn parse(input: &str) -> Result<Grid<u8>, SolveError> {
+    Grid::parse(input, |d| d.to_digit(10).map(|d| d as u8))
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd)]
@@ -57,34 +52,13 @@ struct State {
     count: u8,
 }
 
-fn next_states1(map: &[Vec<u8>], state: &State) -> Vec<State> {
-    let directions = match state.dir {
-        Direction::Up => [Direction::Left, Direction::Up, Direction::Right],
-        Direction::Down => [Direction::Right, Direction::Down, Direction::Left],
-        Direction::Left => [Direction::Down, Direction::Left, Direction::Up],
-        Direction::Right => [Direction::Up, Direction::Right, Direction::Down],
-    };
+fn next_states1(map: &Grid<u8>, state: &State) -> Vec<State> {
+    let directions = [state.dir.turn_left(), state.dir, state.dir.turn_right()];
 
     directions
         .into_iter()
         .filter_map(|dir| {
-            let (dx, dy) = match dir {
-                Direction::Up => (0, -1),
-                Direction::Down => (0, 1),
-                Direction::Left => (-1, 0),
-                Direction::Right => (1, 0),
-            };
-
-            let (next_x, next_y) = (state.x as i32 + dx, state.y as i32 + dy);
-            if next_x < 0
-                || next_y < 0
-                || next_x >= map[0].len() as i32
-                || next_y >= map.len() as i32
-            {
-                return None;
-            }
-
-            let (next_x, next_y) = (next_x as usize, next_y as usize);
+            let (next_x, next_y) = map.offset((state.x, state.y), dir.delta())?;
 
             if dir == state.dir && state.count >= 3 {
                 return None;
@@ -99,34 +73,13 @@ fn next_states1(map: &[Vec<u8>], state: &State) -> Vec<State> {
         })
         .collect()
 }
-fn next_states2(map: &[Vec<u8>], state: &State) -> Vec<State> {
-    let directions = match state.dir {
-        Direction::Up => [Direction::Left, Direction::Up, Direction::Right],
-        Direction::Down => [Direction::Right, Direction::Down, Direction::Left],
-        Direction::Left => [Direction::Down, Direction::Left, Direction::Up],
-        Direction::Right => [Direction::Up, Direction::Right, Direction::Down],
-    };
+fn next_states2(map: &Grid<u8>, state: &State) -> Vec<State> {
+    let directions = [state.dir.turn_left(), state.dir, state.dir.turn_right()];
 
     directions
         .into_iter()
         .filter_map(|dir| {
-            let (dx, dy) = match dir {
-                Direction::Up => (0, -1),
-                Direction::Down => (0, 1),
-                Direction::Left => (-1, 0),
-                Direction::Right => (1, 0),
-            };
-
-            let (next_x, next_y) = (state.x as i32 + dx, state.y as i32 + dy);
-            if next_x < 0
-                || next_y < 0
-                || next_x >= map[0].len() as i32
-                || next_y >= map.len() as i32
-            {
-                return None;
-            }
-
-            let (next_x, next_y) = (next_x as usize, next_y as usize);
+            let (next_x, next_y) = map.offset((state.x, state.y), dir.delta())?;
 
             // Min 4 moves in the same direction before turning
             if dir != state.dir && state.count < 4 {
@@ -148,69 +101,57 @@ fn next_states2(map: &[Vec<u8>], state: &State) -> Vec<State> {
         .collect()
 }
 
-fn shortest_paths(
-    map: &[Vec<u8>],
-    next_states: fn(&[Vec<u8>], &State) -> Vec<State>,
-) -> HashMap<State, u32> {
-    let initial_state_r = State {
-        x: 0,
-        y: 0,
-        dir: Direction::Right,
-        count: 0,
-    };
-    let initial_state_d = State {
+fn least_heat_loss(
+    map: &Grid<u8>,
+    next_states: fn(&Grid<u8>, &State) -> Vec<State>,
+    min_run: u8,
+) -> Result<u32, SolveError> {
+    let start = State {
         x: 0,
         y: 0,
         dir: Direction::Right,
         count: 0,
     };
 
-    let mut visited = HashSet::new();
-    let mut distances = HashMap::from([(initial_state_r, 0), (initial_state_d, 0)]);
-    let mut q = BinaryHeap::from([(0i32, initial_state_r), (0i32, initial_state_d)]);
-
-    while let Some((_, state)) = q.pop() {
-        if !visited.insert(state) {
-            continue;
-        }
+    dijkstra(
+        start,
+        |state| {
+            next_states(map, state)
+                .into_iter()
+                .map(|next| (next, map[(next.x, next.y)] as u32))
+        },
+        |&State { x, y, count, .. }| {
+            x == map.width() - 1 && y == map.height() - 1 && count >= min_run
+        },
+    )
+    .map(|path| path.cost)
+    .ok_or_else(|| SolveError::no_solution("the crucible cannot reach the factory"))
+}
 
-        let heat_loss = *distances.get(&state).unwrap();
-        for next_state in next_states(map, &state) {
-            let next_heat_loss = heat_loss + map[next_state.y][next_state.x] as u32;
-            if next_heat_loss < *distances.get(&next_state).unwrap_or(&u32::MAX) {
-                distances.insert(next_state, next_heat_loss);
-                q.push((
-                    // Negate so we use BinaryHeap as a min-heap
-                    -(next_heat_loss as i32),
-                    next_state,
-                ));
-            }
-        }
-    }
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let map = parse(input)?;
 
-    distances
+    least_heat_loss(&map, next_states1, 0)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let map = parse(input);
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let map = parse(input)?;
 
-    *shortest_paths(&map, next_states1)
-        .iter()
-        .filter(|(&State { x, y, .. }, _)| x == map[0].len() - 1 && y == map.len() - 1)
-        .map(|(_, d)| d)
-        .min()
-        .unwrap()
+    least_heat_loss(&map, next_states2, 4)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let map = parse(input);
+pub struct Solver;
 
-    *shortest_paths(&map, next_states2)
-        .iter()
-        .filter(|(&State { x, y, count, .. }, _)| {
-            x == map[0].len() - 1 && y == map.len() - 1 && count >= 4
-        })
-        .map(|(_, d)| d)
-        .min()
-        .unwrap()
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,27 +1,39 @@
-use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    character::complete::{char, newline, u8},
-    combinator::map,
-    multi::separated_list1,
+    character::complete::{char, u8},
+    combinator::{map, map_res},
     sequence::separated_pair,
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    geometry::{perimeter, shoelace_area, Direction, Point2},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const EXAMPLE: &str = include_str!("example.in");
 
+    #[test]
+    fn it_digs_trenches_enclosing_nothing() {
+        let input = "R 4 (#000040)\nL 4 (#000042)";
+        assert_eq!(solve_part1(input), Ok(5));
+        assert_eq!(solve_part2(input), Ok(5));
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 62);
+            assert_eq!(result, Ok(62));
         }
     }
 
@@ -31,19 +43,11 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 952408144115);
+            assert_eq!(result, Ok(952408144115));
         }
     }
 }
 
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
 #[derive(Debug)]
 struct Instruction {
     dir: Direction,
@@ -60,7 +64,7 @@ fn parse_direction(input: &str) -> IResult<&str, Direction> {
 }
 fn parse_color(input: &str) -> IResult<&str, Instruction> {
     let (input, _) = tag("(#")(input)?;
-    let (input, n) = take(5usize)(input)?;
+    let (input, n) = map_res(take(5usize), |n| usize::from_str_radix(n, 16))(input)?;
     let (input, dir) = alt((
         map(char('0'), |_| Direction::Right),
         map(char('1'), |_| Direction::Down),
@@ -69,7 +73,6 @@ fn parse_color(input: &str) -> IResult<&str, Instruction> {
     ))(input)?;
     let (input, _) = char(')')(input)?;
 
-    let n = usize::from_str_radix(n, 16).unwrap();
     Ok((input, Instruction { dir, n }))
 }
 fn parse_instruction(input: &str) -> IResult<&str, (Instruction, Instruction)> {
@@ -82,43 +85,50 @@ fn parse_instruction(input: &str) -> IResult<&str, (Instruction, Instruction)> {
     Ok((input, (instruction1, instruction2)))
 }
 fn parse(input: &str) -> IResult<&str, Vec<(Instruction, Instruction)>> {
-    separated_list1(newline, parse_instruction)(input)
+    lines(parse_instruction)(input)
 }
 
 fn solve(dig_plan: &[&Instruction]) -> u64 {
-    let mut x = 0;
-    let mut y = 0;
-    let mut points = vec![];
-    let mut perimeter = 0;
-
-    for instruction in dig_plan {
-        points.push((x, y));
-        perimeter += instruction.n as u64;
-        match instruction.dir {
-            Direction::Up => y += instruction.n as i32,
-            Direction::Right => x += instruction.n as i32,
-            Direction::Down => y -= instruction.n as i32,
-            Direction::Left => x -= instruction.n as i32,
-        }
-    }
+    let vertices = dig_plan
+        .iter()
+        .scan(Point2::ORIGIN, |pos, instruction| {
+            let vertex = *pos;
+            *pos += instruction.dir * instruction.n as i64;
+            Some(vertex)
+        })
+        .collect::<Vec<_>>();
+
+    // The trench itself is dug out too, so count its squares along with the ones it encloses.
+    // That's Pick's theorem with the boundary added back, which also holds for a trench that
+    // encloses nothing.
+    let boundary = perimeter(&vertices);
+    shoelace_area(&vertices) + boundary / 2 + 1
+}
 
-    let area = (points
-        .into_iter()
-        .circular_tuple_windows()
-        .map(|((x1, y1), (x2, y2))| x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64)
-        .sum::<i64>()
-        / 2)
-    .unsigned_abs();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let dig_plan = parse_all(input, parse)?;
 
-    perimeter / 2 + area + 1
+    Ok(solve(&dig_plan.iter().map(|(i, _)| i).collect::<Vec<_>>()) as u32)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, dig_plan) = parse(input).unwrap();
-    solve(&dig_plan.iter().map(|(i, _)| i).collect::<Vec<_>>()) as u32
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let dig_plan = parse_all(input, parse)?;
+
+    Ok(solve(&dig_plan.iter().map(|(_, i)| i).collect::<Vec<_>>()))
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    let (_, dig_plan) = parse(input).unwrap();
-    solve(&dig_plan.iter().map(|(_, i)| i).collect::<Vec<_>>())
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,7 +1,8 @@
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, char, newline, u32},
+    character::complete::{alpha1, char, u32},
     combinator::map,
     multi::separated_list1,
     sequence::{delimited, separated_pair},
@@ -9,19 +10,122 @@ use nom::{
 };
 use std::collections::HashMap;
 
+use crate::{
+    dot::Dot,
+    error::{parse_all, SolveError},
+    parsers::{blank_line, lines},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     const EXAMPLE: &str = include_str!("example.in");
 
+    // Each workflow only sends parts on to later ones, so every part ends up accepted or rejected
+    fn system() -> impl Strategy<Value = String> {
+        let rule = (0..4usize, any::<bool>(), 1..=4000u32, any::<usize>());
+        let workflow = (prop::collection::vec(rule, 0..4), any::<usize>());
+        let part = prop::collection::vec(1..=4000u32, 4);
+
+        (
+            prop::collection::vec(workflow, 1..8),
+            prop::collection::vec(part, 1..10),
+        )
+            .prop_map(|(workflows, parts)| {
+                let name = |i: usize| match i {
+                    0 => "in".to_string(),
+                    _ => "w".repeat(i),
+                };
+                let target = |from: usize, pick: usize| match pick % (workflows.len() - from + 1) {
+                    0 => "A".to_string(),
+                    1 => "R".to_string(),
+                    n => name(from + n - 1),
+                };
+
+                let mut workflows = workflows.iter().enumerate().map(|(i, (rules, fallback))| {
+                    let rules = rules.iter().map(|&(attribute, gt, value, pick)| {
+                        let attribute = ['x', 'm', 'a', 's'][attribute];
+                        let op = if gt { '>' } else { '<' };
+                        format!("{attribute}{op}{value}:{}", target(i, pick))
+                    });
+                    let rules = rules.chain([target(i, *fallback)]).join(",");
+                    format!("{}{{{rules}}}", name(i))
+                });
+                let mut parts = parts
+                    .iter()
+                    .map(|p| format!("{{x={},m={},a={},s={}}}", p[0], p[1], p[2], p[3]));
+
+                format!("{}\n\n{}", workflows.join("\n"), parts.join("\n"))
+            })
+    }
+
+    // Swaps every "A" and "R" target, which flips the outcome for every part
+    fn invert(system: &str) -> String {
+        system
+            .split_inclusive(|c: char| !c.is_alphabetic())
+            .map(|token| match token {
+                "A}" => "R}",
+                "R}" => "A}",
+                "A," => "R,",
+                "R," => "A,",
+                token => token,
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn it_splits_parts_between_outcomes(system in system()) {
+            let (_, parts) = parse_system(&system).unwrap();
+            let total = parts.iter().map(Part::rating).sum::<u32>();
+
+            let accepted = solve_part1(&system).unwrap();
+            let rejected = solve_part1(&invert(&system)).unwrap();
+            prop_assert_eq!(accepted + rejected, total);
+        }
+
+        #[test]
+        fn it_splits_combinations_between_outcomes(system in system()) {
+            let accepted = solve_part2(&system).unwrap();
+            let rejected = solve_part2(&invert(&system)).unwrap();
+            prop_assert_eq!(accepted + rejected, 4000u64.pow(4));
+        }
+    }
+
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE.replacen("qkq", "qkz", 1);
+        assert_eq!(
+            solve_part1(&input),
+            Err(SolveError::Parse {
+                line: 1,
+                column: 11,
+                message: "unknown workflow qkz".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn it_exports_dot() {
+        let (workflows, _) = parse_system(EXAMPLE).unwrap();
+        let dot = dot(&workflows).to_string();
+
+        assert!(dot.starts_with("digraph workflows {\n    \"in\" [shape=\"invhouse\"];\n"));
+        assert!(dot.contains("    \"in\" -> \"px\" [label=\"s<1351\"];\n    \"in\" -> \"qqz\";\n"));
+        assert!(dot.contains("    \"pv\" -> \"R\" [label=\"a>1716\"];\n    \"pv\" -> \"A\";\n"));
+        assert_eq!(dot.matches(" -> ").count(), 25);
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 19114);
+            assert_eq!(result, Ok(19114));
         }
     }
 
@@ -31,7 +135,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 167409079868000);
+            assert_eq!(result, Ok(167409079868000));
         }
     }
 }
@@ -44,6 +148,17 @@ enum Attribute {
     S,
 }
 
+impl Attribute {
+    fn name(&self) -> char {
+        match self {
+            Attribute::X => 'x',
+            Attribute::M => 'm',
+            Attribute::A => 'a',
+            Attribute::S => 's',
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 enum Rule {
     Gt(Attribute, u32),
@@ -118,16 +233,16 @@ fn parse_rule(input: &str) -> IResult<&str, (Rule, &str)> {
         map(parse_workflow_name, |goto| (Rule::Goto, goto)),
     ))(input)
 }
-fn parse_rules(input: &str) -> IResult<&str, Workflow> {
+fn parse_rules(input: &str) -> IResult<&str, Workflow<'_>> {
     separated_list1(char(','), parse_rule)(input)
 }
-fn parse_workflow(input: &str) -> IResult<&str, (&str, Workflow)> {
+fn parse_workflow(input: &str) -> IResult<&str, (&str, Workflow<'_>)> {
     let (input, name) = parse_workflow_name(input)?;
     let (input, rules) = delimited(char('{'), parse_rules, char('}'))(input)?;
     Ok((input, (name, rules)))
 }
-fn parse_workflows(input: &str) -> IResult<&str, HashMap<&str, Workflow>> {
-    let (input, workflows) = separated_list1(newline, parse_workflow)(input)?;
+fn parse_workflows(input: &str) -> IResult<&str, HashMap<&str, Workflow<'_>>> {
+    let (input, workflows) = lines(parse_workflow)(input)?;
     Ok((input, workflows.into_iter().collect()))
 }
 fn parse_part(input: &str) -> IResult<&str, Part> {
@@ -139,10 +254,60 @@ fn parse_part(input: &str) -> IResult<&str, Part> {
     Ok((input, Part { x, m, a, s }))
 }
 fn parse_parts(input: &str) -> IResult<&str, Vec<Part>> {
-    separated_list1(newline, parse_part)(input)
+    lines(parse_part)(input)
+}
+fn parse(input: &str) -> IResult<&str, (HashMap<&str, Workflow<'_>>, Vec<Part>)> {
+    separated_pair(parse_workflows, blank_line, parse_parts)(input)
 }
-fn parse(input: &str) -> IResult<&str, (HashMap<&str, Workflow>, Vec<Part>)> {
-    separated_pair(parse_workflows, tag("\n\n"), parse_parts)(input)
+
+fn parse_system(input: &str) -> Result<(HashMap<&str, Workflow<'_>>, Vec<Part>), SolveError> {
+    let (workflows, parts) = parse_all(input, parse)?;
+
+    for workflow in workflows.values() {
+        for (_, goto) in workflow {
+            if !matches!(*goto, "A" | "R") && !workflows.contains_key(goto) {
+                return Err(SolveError::parse(
+                    input,
+                    goto,
+                    format!("unknown workflow {goto}"),
+                ));
+            }
+        }
+    }
+    if !workflows.contains_key("in") {
+        return Err(SolveError::no_solution("no \"in\" workflow"));
+    }
+
+    Ok((workflows, parts))
+}
+
+// Each rule is an edge labelled with its condition, the fallback's edge has no label
+fn dot(workflows: &HashMap<&str, Workflow>) -> Dot {
+    let mut dot = Dot::digraph("workflows");
+    dot.node("in", &[("shape", "invhouse")])
+        .node("A", &[("shape", "doublecircle"), ("color", "green")])
+        .node("R", &[("shape", "doublecircle"), ("color", "red")]);
+
+    let workflows = workflows.iter().sorted_by_key(|(name, _)| **name);
+    for (name, rules) in workflows {
+        if *name != "in" {
+            dot.node(name, &[("shape", "box")]);
+        }
+
+        for (rule, goto) in rules {
+            let condition = match rule {
+                Rule::Gt(attr, value) => format!("{}>{value}", attr.name()),
+                Rule::Lt(attr, value) => format!("{}<{value}", attr.name()),
+                Rule::Goto => {
+                    dot.edge(name, goto, &[]);
+                    continue;
+                }
+            };
+            dot.edge(name, goto, &[("label", &condition)]);
+        }
+    }
+
+    dot
 }
 
 fn is_accepted(workflows: &HashMap<&str, Workflow>, part: &Part) -> bool {
@@ -175,13 +340,14 @@ fn is_accepted(workflows: &HashMap<&str, Workflow>, part: &Part) -> bool {
         }
     }
 }
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, (workflows, parts)) = parse(input).unwrap();
-    parts
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let (workflows, parts) = parse_system(input)?;
+
+    Ok(parts
         .iter()
         .filter(|part| is_accepted(&workflows, part))
         .map(|part| part.rating())
-        .sum()
+        .sum())
 }
 
 fn reject_paths(
@@ -227,8 +393,8 @@ fn reject_paths(
     }
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    let (_, (workflows, _)) = parse(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let (workflows, _) = parse_system(input)?;
 
     let mut paths = vec![];
     reject_paths(&workflows, "in", &mut vec![], &mut paths);
@@ -251,35 +417,55 @@ pub fn solve_part2(input: &str) -> u64 {
                     min_x = min_x.max(value + 1);
                 }
                 Rule::Lt(Attribute::X, value) => {
-                    max_x = max_x.min(value - 1);
+                    max_x = max_x.min(value.saturating_sub(1));
                 }
                 Rule::Gt(Attribute::M, value) => {
                     min_m = min_m.max(value + 1);
                 }
                 Rule::Lt(Attribute::M, value) => {
-                    max_m = max_m.min(value - 1);
+                    max_m = max_m.min(value.saturating_sub(1));
                 }
                 Rule::Gt(Attribute::A, value) => {
                     min_a = min_a.max(value + 1);
                 }
                 Rule::Lt(Attribute::A, value) => {
-                    max_a = max_a.min(value - 1);
+                    max_a = max_a.min(value.saturating_sub(1));
                 }
                 Rule::Gt(Attribute::S, value) => {
                     min_s = min_s.max(value + 1);
                 }
                 Rule::Lt(Attribute::S, value) => {
-                    max_s = max_s.min(value - 1);
+                    max_s = max_s.min(value.saturating_sub(1));
                 }
                 _ => {}
             }
         }
 
-        combinations -= (max_x - min_x + 1) as u64
-            * (max_m - min_m + 1) as u64
-            * (max_a - min_a + 1) as u64
-            * (max_s - min_s + 1) as u64;
+        // Contradicting rules leave an empty range rather than a negative one
+        let width = |min: u32, max: u32| u64::from((max + 1).saturating_sub(min));
+        combinations -=
+            width(min_x, max_x) * width(min_m, max_m) * width(min_a, max_a) * width(min_s, max_s);
+    }
+
+    Ok(combinations)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_system(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
     }
 
-    combinations
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+
+    fn dot(&self, input: &str) -> Option<Result<Dot, SolveError>> {
+        Some(parse_system(input).map(|(workflows, _)| dot(&workflows)))
+    }
 }
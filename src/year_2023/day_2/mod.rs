@@ -1,13 +1,19 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{newline, u8},
+    character::complete::u8,
     combinator::map,
     multi::separated_list1,
     sequence::{delimited, separated_pair},
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,7 +26,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 8);
+            assert_eq!(result, Ok(8));
         }
     }
 
@@ -30,7 +36,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 2286);
+            assert_eq!(result, Ok(2286));
         }
     }
 }
@@ -101,17 +107,17 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
 }
 
 fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    separated_list1(newline, parse_game)(input)
+    lines(parse_game)(input)
 }
 
 const RED_THRESHOLD: u8 = 12;
 const GREEN_THRESHOLD: u8 = 13;
 const BLUE_THRESHOLD: u8 = 14;
 
-pub fn solve_part1(input: &str) -> u32 {
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
     let mut sum = 0;
 
-    let (_, games) = parse_games(input).unwrap();
+    let games = parse_all(input, parse_games)?;
 
     for game in games {
         if game.rounds.iter().all(|round| {
@@ -123,13 +129,13 @@ pub fn solve_part1(input: &str) -> u32 {
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
     let mut sum = 0;
 
-    let (_, games) = parse_games(input).unwrap();
+    let games = parse_all(input, parse_games)?;
 
     for game in games {
         let red = game.rounds.iter().map(|round| round.red).max().unwrap_or(0);
@@ -149,5 +155,21 @@ pub fn solve_part2(input: &str) -> u32 {
         sum += red as u32 * green as u32 * blue as u32;
     }
 
-    sum
+    Ok(sum)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse_games).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,14 +1,24 @@
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, char, newline},
+    character::complete::{alpha1, char},
     combinator::map,
     multi::separated_list1,
     sequence::{preceded, separated_pair},
     IResult,
 };
-use num::integer::lcm;
-use std::collections::{HashMap, VecDeque};
+use num::Integer;
+use std::collections::{hash_map::Entry, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::{
+    cycles::{Cycle, Periodic},
+    dot::Dot,
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+    trace_event,
+};
 
 #[cfg(test)]
 mod tests {
@@ -16,6 +26,116 @@ mod tests {
 
     const EXAMPLE_1: &str = include_str!("example_1.in");
     const EXAMPLE_2: &str = include_str!("example_2.in");
+    const EXAMPLE_3: &str = include_str!("example_3.in");
+    const EXAMPLE_4: &str = include_str!("example_4.in");
+
+    fn press_until_rx(input: &str) -> Option<usize> {
+        Network::parse(input)
+            .unwrap()
+            .first_press("rx", Pulse::Low, 1000)
+    }
+
+    #[test]
+    fn it_reports_malformed_input() {
+        let input = EXAMPLE_1.replace("&inv", "*inv");
+        assert_eq!(
+            solve_part1(&input),
+            Err(SolveError::Parse {
+                line: 5,
+                column: 1,
+                message: "unexpected input".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn it_reports_networks_without_rx() {
+        assert_eq!(
+            solve_part2(EXAMPLE_1),
+            Err(SolveError::NoSolution(
+                "no module sends pulses to rx".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn it_counts_pulses_per_edge() {
+        let mut network = Network::parse(EXAMPLE_1).unwrap();
+        network.press();
+
+        assert_eq!(
+            network.pulses("broadcaster", "a"),
+            PulseCount { low: 1, high: 0 }
+        );
+        assert_eq!(network.pulses("inv", "a"), PulseCount { low: 1, high: 1 });
+        assert_eq!(network.pulses("a", "c"), PulseCount::default());
+        assert_eq!(network.total(), PulseCount { low: 8, high: 4 });
+    }
+
+    #[test]
+    fn it_tracks_module_state() {
+        let mut network = Network::parse(EXAMPLE_2).unwrap();
+        let sent = network.press();
+
+        assert_eq!(
+            sent.last(),
+            Some(&Sent {
+                from: "con",
+                to: "output",
+                pulse: Pulse::Low
+            })
+        );
+        assert_eq!(network.module("a"), Some(&Module::FlipFlop(true)));
+        assert_eq!(
+            network.module("con"),
+            Some(&Module::Conjunction(HashMap::from([
+                ("a", Pulse::High),
+                ("b", Pulse::High)
+            ])))
+        );
+
+        network.press_n(3);
+        assert_eq!(network.presses(), 4);
+        assert_eq!(network.module("a"), Some(&Module::FlipFlop(false)));
+        assert_eq!(network.module("nope"), None);
+    }
+
+    #[test]
+    fn it_finds_the_first_press_a_module_gets_a_pulse() {
+        let mut network = Network::parse(EXAMPLE_3).unwrap();
+
+        assert_eq!(network.first_press("y", Pulse::Low, 100), Some(3));
+        assert_eq!(network.first_press("b", Pulse::Low, 100), Some(2));
+        assert_eq!(network.first_press("rx", Pulse::Low, 10), None);
+        assert_eq!(network.presses(), 10);
+        assert_eq!(network.predict_low("y"), Ok(3));
+        assert_eq!(network.predict_low("rx"), Ok(15));
+        assert_eq!(network.presses(), 10);
+    }
+
+    #[test]
+    fn it_exports_dot() {
+        let network = Network::parse(EXAMPLE_2).unwrap();
+
+        assert_eq!(
+            network.dot().to_string(),
+            "digraph modules {
+    \"a\" [label=\"%a\", shape=\"box\"];
+    \"b\" [label=\"%b\", shape=\"box\"];
+    \"broadcaster\" [label=\"broadcaster\", shape=\"doublecircle\"];
+    \"con\" [label=\"&con\", shape=\"diamond\"];
+    \"inv\" [label=\"&inv\", shape=\"diamond\"];
+    \"output\" [label=\"output\", shape=\"ellipse\"];
+    \"a\" -> \"inv\";
+    \"a\" -> \"con\";
+    \"b\" -> \"con\";
+    \"broadcaster\" -> \"a\";
+    \"con\" -> \"output\";
+    \"inv\" -> \"b\";
+}
+"
+        );
+    }
 
     mod part1 {
         use super::*;
@@ -23,49 +143,134 @@ mod tests {
         #[test]
         fn it_solves_example_1() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 32000000);
+            assert_eq!(result, Ok(32000000));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part1(EXAMPLE_2);
-            assert_eq!(result, 11687500);
+            assert_eq!(result, Ok(11687500));
+        }
+    }
+
+    mod part2 {
+        use super::*;
+
+        #[test]
+        fn it_solves_example_3() {
+            let result = solve_part2(EXAMPLE_3);
+            assert_eq!(result, Ok(15));
+            assert_eq!(press_until_rx(EXAMPLE_3), Some(15));
+        }
+
+        #[test]
+        fn it_combines_offset_circuits() {
+            let result = solve_part2(EXAMPLE_4);
+            assert_eq!(result, Ok(10));
+            assert_eq!(press_until_rx(EXAMPLE_4), Some(10));
+        }
+
+        #[test]
+        fn it_watches_shared_circuits_whole() {
+            let input = EXAMPLE_2.replace("output", "rx");
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(press_until_rx(&input).unwrap() as u64));
+        }
+
+        #[test]
+        fn it_combines_inputs_held_high() {
+            let input = "broadcaster -> a, b\n%a -> out\n%b -> out\n&out -> rx";
+            let result = solve_part2(input);
+            assert_eq!(result, Ok(1));
+            assert_eq!(press_until_rx(input), Some(1));
+        }
+
+        #[test]
+        fn it_counts_inputs_held_from_earlier_presses() {
+            let input = "broadcaster -> a, c\n%a -> b\n%b -> out\n%c -> out\n&out -> rx";
+            let result = solve_part2(input);
+            assert_eq!(result, Ok(3));
+            assert_eq!(press_until_rx(input), Some(3));
+        }
+
+        #[test]
+        fn it_checks_inputs_held_high_for_most_of_their_cycle() {
+            // The top bits of 8, 9 and 10 bit counters are each held high for half their cycle
+            let counters = [('x', 8), ('y', 9), ('z', 10)].map(|(counter, bits)| {
+                let names = ('a'..).take(bits).map(|bit| format!("{counter}{bit}"));
+                names
+                    .chain(["out".to_string()])
+                    .tuple_windows()
+                    .map(|(bit, next)| format!("%{bit} -> {next}"))
+                    .join("\n")
+            });
+            let input = format!(
+                "broadcaster -> xa, ya, za\n{}\n&out -> rx",
+                counters.join("\n")
+            );
+            let result = solve_part2(&input);
+            assert_eq!(result, Ok(128 + 256 + 512));
+            assert_eq!(press_until_rx(&input), Some(128 + 256 + 512));
+        }
+
+        #[test]
+        fn it_inverts_an_input_held_high() {
+            let input = "broadcaster -> a\n%a -> inv\n&inv -> rx";
+            let result = solve_part2(input);
+            assert_eq!(result, Ok(1));
+            assert_eq!(press_until_rx(input), Some(1));
+        }
+
+        #[test]
+        fn it_reports_circuits_without_a_cycle() {
+            // A ripple counter with 17 bits only wraps around after 131072 presses
+            let counter = ('a'..='q')
+                .tuple_windows()
+                .map(|(bit, next)| format!("%{bit} -> {next}"))
+                .join("\n");
+            let input = format!("broadcaster -> a\n{counter}\n%q -> rx");
+            assert_eq!(
+                solve_part2(&input),
+                Err(SolveError::NoSolution(
+                    "q doesn't repeat within 65536 presses".to_string()
+                ))
+            );
         }
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-enum Pulse {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
     Low,
     High,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum Module<'a> {
+pub enum Module<'a> {
     Broadcast,
     FlipFlop(bool),
     Conjunction(HashMap<&'a str, Pulse>),
     Untyped,
 }
 
-fn parse(input: &str) -> IResult<&str, (HashMap<&str, Module>, HashMap<&str, Vec<&str>>)> {
-    let (input, rows) = separated_list1(
-        newline,
-        separated_pair(
-            alt((
-                map(tag("broadcaster"), |_| ("broadcaster", Module::Broadcast)),
-                map(preceded(char('%'), alpha1), |name| {
-                    (name, Module::FlipFlop(false))
-                }),
-                map(preceded(char('&'), alpha1), |name| {
-                    (name, Module::Conjunction(HashMap::new()))
-                }),
-                map(alpha1, |name| (name, Module::Untyped)),
-            )),
-            tag(" -> "),
-            separated_list1(tag(", "), alpha1),
-        ),
-    )(input)?;
+type Modules<'a> = HashMap<&'a str, Module<'a>>;
+type Configurations<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+fn parse(input: &str) -> IResult<&str, (Modules<'_>, Configurations<'_>)> {
+    let (input, rows) = lines(separated_pair(
+        alt((
+            map(tag("broadcaster"), |_| ("broadcaster", Module::Broadcast)),
+            map(preceded(char('%'), alpha1), |name| {
+                (name, Module::FlipFlop(false))
+            }),
+            map(preceded(char('&'), alpha1), |name| {
+                (name, Module::Conjunction(HashMap::new()))
+            }),
+            map(alpha1, |name| (name, Module::Untyped)),
+        )),
+        tag(" -> "),
+        separated_list1(tag(", "), alpha1),
+    ))(input)?;
 
     let mut modules = rows
         .iter()
@@ -92,114 +297,520 @@ fn parse(input: &str) -> IResult<&str, (HashMap<&str, Module>, HashMap<&str, Vec
     Ok((input, (modules, configurations)))
 }
 
-fn push_button<'a>(
-    modules: &mut HashMap<&str, Module<'a>>,
-    configurations: &HashMap<&str, Vec<&'a str>>,
-    rx_conjunction: Option<&'a str>,
-) -> (u32, u32, Option<&'a str>) {
-    let mut low = 0;
-    let mut high = 0;
-    let mut conj_input = None;
+fn parse_network(input: &str) -> Result<(Modules<'_>, Configurations<'_>), SolveError> {
+    let (modules, configurations) = parse_all(input, parse)?;
 
-    let mut q = VecDeque::from([("button", "broadcaster", Pulse::Low)]);
+    if modules.get("broadcaster") != Some(&Module::Broadcast) {
+        return Err(SolveError::no_solution("no broadcaster module"));
+    }
 
-    while let Some((prev_name, curr_name, pulse)) = q.pop_back() {
-        match pulse {
-            Pulse::Low => low += 1,
-            Pulse::High => high += 1,
-        }
+    Ok((modules, configurations))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sent<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub pulse: Pulse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PulseCount {
+    pub low: u64,
+    pub high: u64,
+}
+
+// The modules wired up as in the input, keeping track of every pulse sent since it was built
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    modules: Modules<'a>,
+    configurations: Configurations<'a>,
+    presses: usize,
+    counts: HashMap<(&'a str, &'a str), PulseCount>,
+    first_received: HashMap<(&'a str, Pulse), usize>,
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Self, SolveError> {
+        let (modules, configurations) = parse_network(input)?;
+
+        Ok(Network {
+            modules,
+            configurations,
+            presses: 0,
+            counts: HashMap::new(),
+            first_received: HashMap::new(),
+        })
+    }
+
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    pub fn module(&self, name: &str) -> Option<&Module<'a>> {
+        self.modules.get(name)
+    }
+
+    // The pulses sent along one edge so far
+    pub fn pulses(&self, from: &str, to: &str) -> PulseCount {
+        self.counts.get(&(from, to)).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> PulseCount {
+        self.counts
+            .values()
+            .fold(PulseCount::default(), |total, count| PulseCount {
+                low: total.low + count.low,
+                high: total.high + count.high,
+            })
+    }
 
-        if let Some(rx_conjunction) = rx_conjunction {
-            if curr_name == rx_conjunction && pulse == Pulse::High {
-                conj_input = Some(prev_name);
+    // Pushes the button once, giving every pulse in the order it was handled
+    pub fn press(&mut self) -> Vec<Sent<'a>> {
+        self.presses += 1;
+        let mut sent = vec![];
+
+        let mut q = VecDeque::from([("button", "broadcaster", Pulse::Low)]);
+
+        while let Some((prev_name, curr_name, pulse)) = q.pop_back() {
+            trace_event!("pulse sent", from = prev_name, to = curr_name, pulse);
+
+            let count = self.counts.entry((prev_name, curr_name)).or_default();
+            match pulse {
+                Pulse::Low => count.low += 1,
+                Pulse::High => count.high += 1,
+            }
+            self.first_received
+                .entry((curr_name, pulse))
+                .or_insert(self.presses);
+            sent.push(Sent {
+                from: prev_name,
+                to: curr_name,
+                pulse,
+            });
+
+            let module = self.modules.get_mut(curr_name).unwrap();
+            let destinations = self.configurations.get(curr_name);
+
+            match (module, pulse) {
+                (Module::Broadcast, pulse) => destinations
+                    .unwrap()
+                    .iter()
+                    .for_each(|destination| q.push_front((curr_name, destination, pulse))),
+
+                (Module::FlipFlop(_), Pulse::High) => (),
+                (Module::FlipFlop(state), Pulse::Low) => {
+                    destinations.unwrap().iter().for_each(|destination| {
+                        q.push_front((
+                            curr_name,
+                            destination,
+                            if *state { Pulse::Low } else { Pulse::High },
+                        ))
+                    });
+                    *state = !*state;
+                }
+
+                (Module::Conjunction(inputs), pulse) => {
+                    *inputs.get_mut(prev_name).unwrap() = pulse;
+                    let next_pulse = if inputs.values().all(|pulse| pulse == &Pulse::High) {
+                        Pulse::Low
+                    } else {
+                        Pulse::High
+                    };
+                    destinations
+                        .unwrap()
+                        .iter()
+                        .for_each(|destination| q.push_front((curr_name, destination, next_pulse)));
+                }
+
+                (Module::Untyped, _) => (),
             }
         }
 
-        let module = modules.get_mut(curr_name).unwrap();
-        let destinations = configurations.get(curr_name);
+        sent
+    }
+
+    pub fn press_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.press();
+        }
+    }
+
+    // The first press on which `name` got `pulse`, pressing on until `limit` presses when it
+    // hasn't yet
+    pub fn first_press(&mut self, name: &str, pulse: Pulse, limit: usize) -> Option<usize> {
+        while !self.first_received.contains_key(&(name, pulse)) && self.presses < limit {
+            self.press();
+        }
+
+        self.first_received.get(&(name, pulse)).copied()
+    }
+
+    // The first press on which `target` gets a low pulse, going on from the presses made so far
+    // and worked out from the circuits feeding it rather than by pressing that often. It gets one
+    // as soon as any module feeding it sends one. A conjunction feeding it is split into the
+    // circuits behind its inputs when they don't share modules, and sends its low pulse once they
+    // all send a high pulse on the same press. This takes those to overlap within that press,
+    // which can't be checked without getting there.
+    pub fn predict_low(&self, target: &str) -> Result<u64, SolveError> {
+        if let Some(&press) = self.first_received.get(&(target, Pulse::Low)) {
+            return Ok(press as u64);
+        }
 
-        match (module, pulse) {
-            (Module::Broadcast, pulse) => destinations
-                .unwrap()
+        let mut inputs = HashMap::<_, Vec<_>>::new();
+        for (name, destinations) in self.configurations.iter().sorted() {
+            for destination in destinations {
+                inputs.entry(*destination).or_default().push(*name);
+            }
+        }
+
+        let Some(feeders) = inputs.get(target) else {
+            return Err(SolveError::no_solution(format!(
+                "no module sends pulses to {target}"
+            )));
+        };
+
+        let mut circuits = vec![];
+        let mut groups = vec![];
+        for &feeder in feeders {
+            let split = match &self.modules[feeder] {
+                Module::Conjunction(conjunction) => conjunction
+                    .keys()
+                    .sorted()
+                    .map(|input| Circuit::new(&inputs, input, feeder, Fire::High))
+                    .collect(),
+                _ => vec![],
+            };
+            let independent = split
                 .iter()
-                .for_each(|destination| q.push_front((curr_name, destination, pulse.clone()))),
-
-            (Module::FlipFlop(_), Pulse::High) => (),
-            (Module::FlipFlop(state), Pulse::Low) => {
-                destinations.unwrap().iter().for_each(|destination| {
-                    q.push_front((
-                        curr_name,
-                        destination,
-                        if *state { Pulse::Low } else { Pulse::High },
-                    ))
-                });
-                *state = !*state;
+                .flat_map(|circuit| &circuit.modules)
+                .all_unique();
+
+            let group = if !split.is_empty() && independent {
+                split
+            } else {
+                let to = self.configurations[feeder]
+                    .iter()
+                    .find(|destination| **destination == target)
+                    .unwrap();
+                vec![Circuit::new(&inputs, feeder, to, Fire::Low)]
+            };
+            groups.push(circuits.len()..circuits.len() + group.len());
+            circuits.extend(group);
+        }
+
+        let firings = watch(&mut self.clone(), &circuits)?;
+
+        groups
+            .into_iter()
+            .map(|group| first_together(&circuits[group.clone()], &firings[group]))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .min()
+            .ok_or_else(|| SolveError::no_solution(format!("{target} never gets a low pulse")))
+    }
+
+    // Flip-flops are boxes and conjunctions diamonds, so the counters feeding `rx` stand out
+    pub fn dot(&self) -> Dot {
+        let mut dot = Dot::digraph("modules");
+
+        for (name, module) in self.modules.iter().sorted_by_key(|(name, _)| **name) {
+            let (label, shape) = match module {
+                Module::Broadcast => (name.to_string(), "doublecircle"),
+                Module::FlipFlop(_) => (format!("%{name}"), "box"),
+                Module::Conjunction(_) => (format!("&{name}"), "diamond"),
+                Module::Untyped => (name.to_string(), "ellipse"),
+            };
+            dot.node(name, &[("label", &label), ("shape", shape)]);
+        }
+        for (name, destinations) in self.configurations.iter().sorted_by_key(|(name, _)| **name) {
+            for destination in destinations {
+                dot.edge(name, destination, &[]);
             }
+        }
+
+        dot
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    let mut network = Network::parse(input)?;
+    network.press_n(1000);
+
+    let total = network.total();
+    Ok(total.low * total.high)
+}
+
+// How long to wait for a circuit to come back to a state it has been in
+const PRESS_LIMIT: usize = 1 << 16;
+
+// How many signals to combine with CRT before checking the remaining circuits press by press,
+// and how many presses to check
+const SIGNAL_LIMIT: usize = 1 << 12;
+const CHECK_LIMIT: usize = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fire {
+    // A high pulse sent during the press, or one held from an earlier press that no low pulse
+    // takes back
+    High,
+    Low,
+}
+
+// A module sending pulses to `to`, along with every module upstream of it. Only the broadcaster
+// is shared with the rest of the network, so the circuit can be watched on its own.
+#[derive(Debug)]
+struct Circuit<'a> {
+    from: &'a str,
+    to: &'a str,
+    fire: Fire,
+    modules: Vec<&'a str>,
+}
 
-            (Module::Conjunction(inputs), pulse) => {
-                *inputs.get_mut(prev_name).unwrap() = pulse;
-                let next_pulse = if inputs.values().all(|pulse| pulse == &Pulse::High) {
-                    Pulse::Low
-                } else {
-                    Pulse::High
-                };
-                destinations.unwrap().iter().for_each(|destination| {
-                    q.push_front((curr_name, destination, next_pulse.clone()))
-                });
+impl<'a> Circuit<'a> {
+    fn new(
+        inputs: &HashMap<&'a str, Vec<&'a str>>,
+        from: &'a str,
+        to: &'a str,
+        fire: Fire,
+    ) -> Self {
+        let mut modules = HashSet::from([from]);
+        let mut stack = vec![from];
+        while let Some(name) = stack.pop() {
+            for &input in inputs.get(name).into_iter().flatten() {
+                if input != "broadcaster" && modules.insert(input) {
+                    stack.push(input);
+                }
             }
+        }
 
-            (Module::Untyped, _) => (),
+        Circuit {
+            from,
+            to,
+            fire,
+            modules: modules.into_iter().sorted().collect(),
         }
     }
 
-    (low, high, conj_input)
+    // Whether `to` still remembers a high pulse from an earlier press, which it keeps until a low
+    // one arrives. That's a matter of the circuit's state, so it repeats along with it.
+    fn held(&self, modules: &Modules) -> bool {
+        match &modules[self.to] {
+            Module::Conjunction(inputs) => inputs[self.from] == Pulse::High,
+            _ => false,
+        }
+    }
+
+    fn state(&self, modules: &Modules) -> Vec<bool> {
+        self.modules
+            .iter()
+            .flat_map(|name| match &modules[name] {
+                Module::FlipFlop(on) => vec![*on],
+                Module::Conjunction(inputs) => inputs
+                    .iter()
+                    .sorted_by_key(|(input, _)| **input)
+                    .map(|(_, pulse)| *pulse == Pulse::High)
+                    .collect(),
+                Module::Broadcast | Module::Untyped => vec![],
+            })
+            .collect()
+    }
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, (mut modules, configurations)) = parse(input).unwrap();
+// The presses a circuit fired on until it came back to an earlier state. Those up to the start of
+// the cycle never come back, later ones repeat every `length` presses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Firings {
+    early: BTreeSet<usize>,
+    residues: BTreeSet<usize>,
+    cycle: Cycle,
+}
+
+impl Firings {
+    fn new(presses: Vec<usize>, cycle: Cycle) -> Self {
+        let (early, late): (Vec<_>, Vec<_>) =
+            presses.into_iter().partition(|&press| press <= cycle.start);
 
-    let mut total_low = 0;
-    let mut total_high = 0;
-    for _ in 0..1000 {
-        let (low, high, _) = push_button(&mut modules, &configurations, None);
-        total_low += low;
-        total_high += high;
+        Firings {
+            early: early.into_iter().collect(),
+            residues: late.into_iter().map(|press| press % cycle.length).collect(),
+            cycle,
+        }
     }
 
-    total_low * total_high
+    fn fires_on(&self, press: usize) -> bool {
+        if press <= self.cycle.start {
+            self.early.contains(&press)
+        } else {
+            self.residues.contains(&(press % self.cycle.length))
+        }
+    }
+
+    // An input that stays high fires on several presses of its cycle, each repeating on its own
+    fn periodic(&self) -> impl Iterator<Item = Periodic> + Clone + '_ {
+        let length = self.cycle.length as u64;
+        self.residues
+            .iter()
+            .map(move |&residue| Periodic::new(residue as u64, length))
+    }
 }
 
-pub fn solve_part2(input: &str) -> u128 {
-    let (_, (mut modules, configurations)) = parse(input).unwrap();
+// Presses the button until every circuit repeats a state
+fn watch<'a>(
+    network: &mut Network<'a>,
+    circuits: &[Circuit<'a>],
+) -> Result<Vec<Firings>, SolveError> {
+    let mut seen = circuits
+        .iter()
+        .map(|circuit| HashMap::from([(circuit.state(&network.modules), network.presses)]))
+        .collect::<Vec<_>>();
+    let mut cycles = vec![None; circuits.len()];
+    let mut presses = vec![vec![]; circuits.len()];
 
-    let rx_conjunction = configurations.iter().find_map(|(name, destinations)| {
-        if destinations == &["rx"] {
-            Some(*name)
-        } else {
-            None
+    for _ in 0..PRESS_LIMIT {
+        if cycles.iter().all(Option::is_some) {
+            break;
         }
-    });
 
-    let input_count = match modules.get(rx_conjunction.unwrap()).unwrap() {
-        Module::Conjunction(map) => map.len(),
-        _ => panic!(),
-    };
+        let held = circuits
+            .iter()
+            .map(|circuit| circuit.held(&network.modules))
+            .collect_vec();
+        let sent = network.press();
+        let press = network.presses;
 
-    let mut cycles = HashMap::new();
+        for (i, circuit) in circuits.iter().enumerate() {
+            if cycles[i].is_some() {
+                continue;
+            }
 
-    for i in 0.. {
-        let (_, _, conj_input) = push_button(&mut modules, &configurations, rx_conjunction);
+            let pulses = sent
+                .iter()
+                .filter(|sent| sent.from == circuit.from && sent.to == circuit.to)
+                .map(|sent| sent.pulse)
+                .collect::<Vec<_>>();
+            let fired = match circuit.fire {
+                Fire::High => {
+                    pulses.contains(&Pulse::High) || held[i] && !pulses.contains(&Pulse::Low)
+                }
+                Fire::Low => pulses.contains(&Pulse::Low),
+            };
+            if fired {
+                presses[i].push(press);
+            }
 
-        if let Some(conj_input) = conj_input {
-            if !cycles.contains_key(conj_input) {
-                cycles.insert(conj_input, i + 1);
+            match seen[i].entry(circuit.state(&network.modules)) {
+                Entry::Occupied(entry) => {
+                    cycles[i] = Some(Cycle {
+                        start: *entry.get(),
+                        length: press - entry.get(),
+                    })
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(press);
+                }
             }
         }
+    }
 
-        if cycles.len() == input_count {
-            break;
+    circuits
+        .iter()
+        .zip(cycles)
+        .zip(presses)
+        .map(|((circuit, cycle), presses)| {
+            let cycle = cycle.ok_or_else(|| {
+                SolveError::no_solution(format!(
+                    "{} doesn't repeat within {PRESS_LIMIT} presses",
+                    circuit.from
+                ))
+            })?;
+            Ok(Firings::new(presses, cycle))
+        })
+        .collect()
+}
+
+// The first press on which every circuit fires, if there is one
+fn first_together(circuits: &[Circuit], firings: &[Firings]) -> Result<Option<u64>, SolveError> {
+    // Until every circuit has started repeating, the presses are checked one by one
+    let start = firings.iter().map(|f| f.cycle.start).max().unwrap_or(0);
+    if let Some(press) = (1..=start).find(|&press| firings.iter().all(|f| f.fires_on(press))) {
+        return Ok(Some(press as u64));
+    }
+
+    // Circuits firing on few presses of their cycle are combined with CRT while that leaves few
+    // enough signals. The rest are checked on each press those signals fire on.
+    let mut sorted = firings.iter().sorted_by_key(|f| f.residues.len());
+    let Some(first) = sorted.next() else {
+        return Ok(None);
+    };
+    let mut signals = first.periodic().collect_vec();
+    let mut checked = vec![];
+    for f in sorted {
+        if signals.len() * f.residues.len() <= SIGNAL_LIMIT {
+            signals = signals
+                .iter()
+                .cartesian_product(f.periodic())
+                .filter_map(|(signal, other)| signal.combine(&other))
+                .collect();
+        } else {
+            checked.push(f);
         }
     }
 
-    cycles.into_values().reduce(lcm).unwrap()
+    let Some(period) = signals.first().map(|signal| signal.period) else {
+        return Ok(None);
+    };
+    // Every signal has the combined period, so each round of it fires them in the same order
+    let after = start as u64 + 1;
+    let presses = signals
+        .iter()
+        .map(|signal| signal.first_after(after))
+        .sorted()
+        .collect_vec();
+    // Past the least common multiple of all the cycles nothing new comes up
+    let repeat = checked.iter().try_fold(period, |repeat, f| {
+        let length = f.cycle.length as u64;
+        repeat.checked_mul(length / repeat.gcd(&length))
+    });
+
+    let candidates =
+        (0..).flat_map(|round| presses.iter().map(move |press| press + round * period));
+    for press in candidates.take(CHECK_LIMIT) {
+        if repeat.is_some_and(|repeat| press >= after.saturating_add(repeat)) {
+            return Ok(None);
+        }
+        if checked.iter().all(|f| f.fires_on(press as usize)) {
+            return Ok(Some(press));
+        }
+    }
+
+    Err(SolveError::no_solution(format!(
+        "the circuits feeding {} don't fire together within {CHECK_LIMIT} checked presses",
+        circuits[0].to
+    )))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    Network::parse(input)?.predict_low("rx")
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_network(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+
+    fn dot(&self, input: &str) -> Option<Result<Dot, SolveError>> {
+        Some(Network::parse(input).map(|network| network.dot()))
+    }
 }
@@ -1,8 +1,12 @@
-use std::collections::{HashSet, VecDeque};
-
-use itertools::Itertools;
 use polyfit_rs::polyfit_rs::polyfit;
 
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    search::distances,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -15,7 +19,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE, 6);
-            assert_eq!(result, 16);
+            assert_eq!(result, Ok(16));
         }
     }
 
@@ -24,28 +28,28 @@ mod tests {
 
         #[test]
         fn it_solves_example_6_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 6, true);
             assert_eq!(result, 16);
         }
 
         #[test]
         fn it_solves_example_10_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 10, true);
             assert_eq!(result, 50);
         }
 
         #[test]
         fn it_solves_example_50_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 50, true);
             assert_eq!(result, 1594);
         }
 
         #[test]
         fn it_solves_example_100_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 100, true);
             assert_eq!(result, 6536);
         }
@@ -53,7 +57,7 @@ mod tests {
         #[test]
         #[ignore]
         fn it_solves_example_500_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 500, true);
             assert_eq!(result, 167004);
         }
@@ -61,7 +65,7 @@ mod tests {
         #[test]
         #[ignore]
         fn it_solves_example_1000_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 1000, true);
             assert_eq!(result, 668697);
         }
@@ -69,118 +73,83 @@ mod tests {
         #[test]
         #[ignore]
         fn it_solves_example_5000_steps() {
-            let (map, start) = parse(EXAMPLE);
+            let (map, start) = parse(EXAMPLE).unwrap();
             let result = reachable_plots(&map, start, 5000, true);
             assert_eq!(result, 16733044);
         }
     }
 }
 
-fn parse(input: &str) -> (Vec<Vec<bool>>, (i32, i32)) {
-    let start = input
-        .lines()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.chars().enumerate().find_map(|(x, c)| match c {
-                'S' => Some((x as i32, y as i32)),
-                _ => None,
-            })
-        })
-        .unwrap();
-
-    let map = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => true,
-                    '.' | 'S' => false,
-                    _ => panic!("Invalid input"),
-                })
-                .collect()
-        })
-        .collect::<Vec<_>>();
-
-    (map, start)
-}
+type Map = Grid<bool>;
 
-fn reachable_plots(
-    map: &[Vec<bool>],
-    start: (i32, i32),
-    target_steps: usize,
-    infinite: bool,
-) -> usize {
-    let width = map[0].len();
-    let height = map.len();
-
-    let mut visited = HashSet::new();
-    let mut q = VecDeque::from([(start, 0)]);
-
-    while let Some(((x, y), steps)) = q.pop_front() {
-        if !visited.insert(((x, y), steps)) {
-            continue;
-        }
+fn parse(input: &str) -> Result<(Map, (i64, i64)), SolveError> {
+    let tiles = Grid::parse(input, |c| matches!(c, '#' | '.' | 'S').then_some(c))?;
 
-        if steps < target_steps {
-            if infinite {
-                let wrapped_x = x.rem_euclid(width as i32);
-                let wrapped_y = y.rem_euclid(height as i32);
-
-                if !map[wrapped_y as usize][(x - 1).rem_euclid(width as i32) as usize] {
-                    q.push_back(((x - 1, y), steps + 1));
-                }
-                if !map[(y - 1).rem_euclid(height as i32) as usize][wrapped_x as usize] {
-                    q.push_back(((x, y - 1), steps + 1));
-                }
-                if !map[wrapped_y as usize][(x + 1).rem_euclid(width as i32) as usize] {
-                    q.push_back(((x + 1, y), steps + 1));
-                }
-                if !map[(y + 1).rem_euclid(height as i32) as usize][wrapped_x as usize] {
-                    q.push_back(((x, y + 1), steps + 1));
-                }
-            } else {
-                if x > 0 && !map[y as usize][x as usize - 1] {
-                    q.push_back(((x - 1, y), steps + 1));
-                }
-                if y > 0 && !map[y as usize - 1][x as usize] {
-                    q.push_back(((x, y - 1), steps + 1));
-                }
-                if (x as usize) < width - 1 && !map[y as usize][x as usize + 1] {
-                    q.push_back(((x + 1, y), steps + 1));
-                }
-                if (y as usize) < height - 1 && !map[y as usize + 1][x as usize] {
-                    q.push_back(((x, y + 1), steps + 1));
-                }
-            }
-        }
-    }
+    let (x, y) = tiles
+        .position(|&c| c == 'S')
+        .ok_or_else(|| SolveError::no_solution("no starting position"))?;
 
-    visited
-        .iter()
-        .filter(|(_, steps)| steps == &target_steps)
-        .map(|((x, y), _)| (x, y))
-        .unique()
+    Ok((tiles.map(|&c| c == '#'), (x as i64, y as i64)))
+}
+
+fn reachable_plots(map: &Map, start: (i64, i64), target_steps: usize, infinite: bool) -> usize {
+    let successors = |&(x, y): &(i64, i64)| {
+        [(x - 1, y), (x, y - 1), (x + 1, y), (x, y + 1)]
+            .into_iter()
+            .filter(|&next| {
+                let rock = if infinite {
+                    Some(map.get_wrapping(next))
+                } else {
+                    map.get_signed(next)
+                };
+
+                rock == Some(&false)
+            })
+    };
+
+    // Any plot reached early can be revisited by stepping back and forth, as long as the parity matches
+    distances(start, successors, target_steps)
+        .values()
+        .filter(|&&steps| steps % 2 == target_steps % 2)
         .count()
 }
 
-pub fn solve_part1(input: &str, steps: usize) -> usize {
-    let (map, start) = parse(input);
-    reachable_plots(&map, start, steps, false)
+pub fn solve_part1(input: &str, steps: usize) -> Result<usize, SolveError> {
+    let (map, start) = parse(input)?;
+
+    Ok(reachable_plots(&map, start, steps, false))
 }
 
-pub fn solve_part2(input: &str) -> usize {
-    let (map, start) = parse(input);
-    let size = map.len();
-    let x0 = reachable_plots(&map, start, size / 2 + size * 0, true);
-    let x1 = reachable_plots(&map, start, size / 2 + size * 1, true);
+pub fn solve_part2(input: &str) -> Result<usize, SolveError> {
+    let (map, start) = parse(input)?;
+    let size = map.height();
+    let x0 = reachable_plots(&map, start, size / 2, true);
+    let x1 = reachable_plots(&map, start, size / 2 + size, true);
     let x2 = reachable_plots(&map, start, size / 2 + size * 2, true);
     let steps = 26501365;
     let xn = ((steps - size / 2) / size) as i64;
-    let result = polyfit(&[0.0, 1.0, 2.0], &[x0 as f32, x1 as f32, x2 as f32], 2).unwrap();
+    let result = polyfit(&[0.0, 1.0, 2.0], &[x0 as f32, x1 as f32, x2 as f32], 2)
+        .map_err(|err| SolveError::no_solution(format!("cannot fit growth curve: {err}")))?;
     let (a, b, c) = (
         result[2].round() as i64,
         result[1].round() as i64,
         result[0].round() as i64,
     );
-    (a * xn.pow(2) + b * xn + c) as usize
+    Ok((a * xn.pow(2) + b * xn + c) as usize)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input, 64).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
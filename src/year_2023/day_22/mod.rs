@@ -2,13 +2,18 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use itertools::Itertools;
 use nom::{
-    character::complete::{char, newline, u32},
-    combinator::map,
-    multi::separated_list1,
+    character::complete::{char, u32},
+    combinator::{map, verify},
     sequence::separated_pair,
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,7 +26,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 5);
+            assert_eq!(result, Ok(5));
         }
     }
 
@@ -31,7 +36,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 7);
+            assert_eq!(result, Ok(7));
         }
     }
 }
@@ -45,10 +50,13 @@ fn parse_coords(input: &str) -> IResult<&str, Coords> {
     )(input)
 }
 fn parse_line(input: &str) -> IResult<&str, (Coords, Coords)> {
-    separated_pair(parse_coords, char('~'), parse_coords)(input)
+    verify(
+        separated_pair(parse_coords, char('~'), parse_coords),
+        |((_, _, z1), (_, _, z2))| *z1 >= 1 && *z2 >= 1,
+    )(input)
 }
 fn parse(input: &str) -> IResult<&str, Vec<(Coords, Coords)>> {
-    separated_list1(newline, parse_line)(input)
+    lines(parse_line)(input)
 }
 
 fn intersects_x_y(
@@ -122,11 +130,11 @@ fn apply_gravity(bricks: &mut [(Coords, Coords)]) -> HashMap<usize, HashSet<usiz
     supported_by
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, mut bricks) = parse(input).unwrap();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let mut bricks = parse_all(input, parse)?;
     let supported_by = apply_gravity(&mut bricks);
 
-    bricks
+    Ok(bricks
         .iter()
         .enumerate()
         .filter(|(i, _)| {
@@ -134,14 +142,14 @@ pub fn solve_part1(input: &str) -> u32 {
                 .values()
                 .all(|supports| !supports.contains(i) || supports.len() > 1)
         })
-        .count() as u32
+        .count() as u32)
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (_, mut bricks) = parse(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let mut bricks = parse_all(input, parse)?;
     let supported_by = apply_gravity(&mut bricks);
 
-    bricks
+    Ok(bricks
         .iter()
         .enumerate()
         .map(|(i, _)| {
@@ -151,15 +159,29 @@ pub fn solve_part2(input: &str) -> u32 {
             while let Some(j) = q.pop_front() {
                 fell.insert(j);
                 for (supported, supports) in &supported_by {
-                    if supports.difference(&fell).count() == 0 {
-                        if fell.insert(*supported) {
-                            q.push_back(*supported);
-                        }
+                    if supports.difference(&fell).count() == 0 && fell.insert(*supported) {
+                        q.push_back(*supported);
                     }
                 }
             }
 
             fell.len() as u32 - 1
         })
-        .sum()
+        .sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
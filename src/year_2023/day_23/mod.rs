@@ -1,6 +1,12 @@
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use itertools::Itertools;
+use crate::{
+    dot::Dot,
+    error::SolveError,
+    search::longest_path,
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -8,13 +14,26 @@ mod tests {
 
     const EXAMPLE: &str = include_str!("example.in");
 
+    #[test]
+    fn it_exports_dot() {
+        let grid = parse(EXAMPLE, true).unwrap();
+        let (start, end) = endpoints(&grid).unwrap();
+        let dot = dot(&build_graph(&grid, &start), start, end).to_string();
+
+        assert!(dot.starts_with("graph trails {\n    \"1,0\" [shape=\"doublecircle\"];\n"));
+        assert!(dot.contains("    \"21,22\" [shape=\"doublecircle\"];\n"));
+        assert!(dot.contains("    \"1,0\" -- \"3,5\" [label=\"15\"];\n"));
+        assert!(dot.contains("    \"19,19\" -- \"21,22\" [label=\"5\"];\n"));
+        assert_eq!(dot.matches(" -- ").count(), 12);
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 94);
+            assert_eq!(result, Ok(94));
         }
     }
 
@@ -24,7 +43,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 154);
+            assert_eq!(result, Ok(154));
         }
     }
 }
@@ -39,23 +58,55 @@ enum Cell {
     DownSlope,
 }
 
-fn parse(input: &str, can_climb: bool) -> Vec<Vec<Cell>> {
-    input
+fn parse(input: &str, can_climb: bool) -> Result<Vec<Vec<Cell>>, SolveError> {
+    let grid = input
         .lines()
         .map(|line| {
-            line.chars()
-                .map(|c| match (c, can_climb) {
-                    ('#', _) => Cell::Wall,
-                    ('.', _) => Cell::Empty,
-                    ('>', true) => Cell::Empty,
-                    ('>', false) => Cell::RightSlope,
-                    ('v', true) => Cell::Empty,
-                    ('v', false) => Cell::DownSlope,
-                    _ => panic!("Invalid cell"),
+            line.char_indices()
+                .map(|(i, c)| match (c, can_climb) {
+                    ('#', _) => Ok(Cell::Wall),
+                    ('.', _) => Ok(Cell::Empty),
+                    ('>', true) => Ok(Cell::Empty),
+                    ('>', false) => Ok(Cell::RightSlope),
+                    ('v', true) => Ok(Cell::Empty),
+                    ('v', false) => Ok(Cell::DownSlope),
+                    _ => Err(SolveError::parse(input, &line[i..], "invalid cell")),
                 })
-                .collect_vec()
+                .collect::<Result<Vec<_>, _>>()
         })
-        .collect_vec()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if grid.len() < 2 || grid[0].len() < 3 {
+        return Err(SolveError::parse(
+            input,
+            input,
+            "expected a map of hiking trails",
+        ));
+    }
+    if let Some(line) = input.lines().find(|line| line.len() != grid[0].len()) {
+        return Err(SolveError::parse(
+            input,
+            line,
+            "rows must have the same width",
+        ));
+    }
+
+    Ok(grid)
+}
+
+fn endpoints(grid: &[Vec<Cell>]) -> Result<(Coords, Coords), SolveError> {
+    let start = (1, 0);
+    let end = (grid[0].len() - 2, grid.len() - 1);
+
+    for (x, y) in [start, end] {
+        if grid[y][x] == Cell::Wall {
+            return Err(SolveError::no_solution(
+                "the trail must start and end on a path",
+            ));
+        }
+    }
+
+    Ok((start, end))
 }
 
 fn compress_graph(edges: &mut HashMap<Coords, Vec<(Coords, usize)>>, start: &Coords) {
@@ -74,7 +125,7 @@ fn compress_graph(edges: &mut HashMap<Coords, Vec<(Coords, usize)>>, start: &Coo
                 .iter()
                 .find(|(neighbor, _)| *neighbor != prev)
                 .unwrap()
-                .clone();
+                .to_owned();
 
             let (_, curr_cost) = edges
                 .get(&prev)
@@ -82,7 +133,7 @@ fn compress_graph(edges: &mut HashMap<Coords, Vec<(Coords, usize)>>, start: &Coo
                 .iter()
                 .find(|(neighbor, _)| *neighbor == curr)
                 .unwrap()
-                .clone();
+                .to_owned();
 
             edges
                 .get_mut(&prev)
@@ -153,44 +204,81 @@ fn build_graph(grid: &[Vec<Cell>], start: &Coords) -> HashMap<Coords, Vec<(Coord
     graph
 }
 
-fn longest_path(
-    edges: &HashMap<Coords, Vec<(Coords, usize)>>,
-    start: &Coords,
-    end: &Coords,
-) -> usize {
-    let mut q = VecDeque::from([(start, 0, HashSet::from([start]))]);
-    let mut result = 0;
-
-    while let Some((curr, cost, nodes)) = q.pop_front() {
-        if curr == end {
-            result = result.max(cost);
-            continue;
-        }
+// The compressed graph of junctions, with each trail labelled by its length. With slopes
+// climbable every trail goes both ways, so it's drawn once.
+fn dot(edges: &HashMap<Coords, Vec<(Coords, usize)>>, start: Coords, end: Coords) -> Dot {
+    let id = |(x, y): Coords| format!("{x},{y}");
+    let mut dot = Dot::graph("trails");
 
-        for (neighbor, new_cost) in &edges[&curr] {
-            if !nodes.contains(neighbor) {
-                let mut new_nodes = nodes.clone();
-                new_nodes.insert(neighbor);
-                q.push_back((neighbor, cost + new_cost, new_nodes));
-            }
+    for &junction in edges.keys().sorted() {
+        if junction == start || junction == end {
+            dot.node(&id(junction), &[("shape", "doublecircle")]);
+        } else {
+            dot.node(&id(junction), &[]);
+        }
+    }
+    for (&from, trails) in edges.iter().sorted() {
+        for &(to, length) in trails.iter().filter(|(to, _)| from < *to) {
+            dot.edge(&id(from), &id(to), &[("label", &length.to_string())]);
         }
     }
 
-    result
+    dot
 }
 
-pub fn solve_part1(input: &str) -> usize {
-    let grid = parse(input, false);
-    let start = (1, 0);
-    let end = (grid[0].len() - 2, grid.len() - 1);
+fn hike(
+    edges: &HashMap<Coords, Vec<(Coords, usize)>>,
+    start: Coords,
+    end: Coords,
+) -> Result<usize, SolveError> {
+    longest_path(
+        start,
+        |curr| edges[curr].iter().copied(),
+        |&curr| curr == end,
+        edges.len(),
+    )
+    .map(|path| path.cost)
+    .ok_or_else(|| SolveError::no_solution("no path leads to the end"))
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, SolveError> {
+    let grid = parse(input, false)?;
+    let (start, end) = endpoints(&grid)?;
+
     let edges = build_graph(&grid, &start);
-    longest_path(&edges, &start, &end)
+
+    hike(&edges, start, end)
 }
 
-pub fn solve_part2(input: &str) -> usize {
-    let grid = parse(input, true);
-    let start = (1, 0);
-    let end = (grid[0].len() - 2, grid.len() - 1);
+pub fn solve_part2(input: &str) -> Result<usize, SolveError> {
+    let grid = parse(input, true)?;
+    let (start, end) = endpoints(&grid)?;
+
     let edges = build_graph(&grid, &start);
-    longest_path(&edges, &start, &end)
+
+    hike(&edges, start, end)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input, false).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+
+    fn dot(&self, input: &str) -> Option<Result<Dot, SolveError>> {
+        let graph = parse(input, true).and_then(|grid| {
+            let (start, end) = endpoints(&grid)?;
+            Ok(dot(&build_graph(&grid, &start), start, end))
+        });
+        Some(graph)
+    }
 }
@@ -1,5 +1,11 @@
 use std::collections::HashSet;
 
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,7 +18,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 4361);
+            assert_eq!(result, Ok(4361));
         }
     }
 
@@ -22,7 +28,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 467835);
+            assert_eq!(result, Ok(467835));
         }
     }
 }
@@ -34,70 +40,37 @@ enum Cell {
     Symbol(char),
 }
 
-fn parse_grid(input: &str) -> Box<[Box<[Cell]>]> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| match c {
-                    '0'..='9' => Cell::Digit(c.to_digit(10).unwrap() as u8),
-                    '.' => Cell::Empty,
-                    _ => Cell::Symbol(c),
-                })
-                .collect::<Vec<_>>()
-                .into_boxed_slice()
+fn parse_grid(input: &str) -> Result<Grid<Cell>, SolveError> {
+    Grid::parse(input, |c| {
+        Some(match c {
+            '0'..='9' => Cell::Digit(c.to_digit(10).unwrap() as u8),
+            '.' => Cell::Empty,
+            _ => Cell::Symbol(c),
         })
-        .collect::<Vec<_>>()
-        .into_boxed_slice()
+    })
 }
 
-fn has_adjacent_symbol(grid: &[Box<[Cell]>], x: usize, y: usize) -> bool {
-    let width = grid[0].len();
-    let height = grid.len();
-
-    let mut has_adjacent_symbol = false;
-
-    for y_offset in -1..=1 {
-        for x_offset in -1..=1 {
-            if x_offset == 0 && y_offset == 0 {
-                continue;
-            }
-
-            let x = x as i32 + x_offset;
-            let y = y as i32 + y_offset;
-
-            if x < 0 || x >= width as i32 || y < 0 || y >= height as i32 {
-                continue;
-            }
-
-            if let Cell::Symbol(_) = grid[y as usize][x as usize] {
-                has_adjacent_symbol = true;
-                break;
-            }
-        }
-    }
-
-    has_adjacent_symbol
+fn has_adjacent_symbol(grid: &Grid<Cell>, x: usize, y: usize) -> bool {
+    grid.neighbors8((x, y))
+        .any(|pos| matches!(grid[pos], Cell::Symbol(_)))
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let grid = parse_grid(input);
-    let width = grid[0].len();
-    let height = grid.len();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_grid(input)?;
 
     let mut sum = 0;
 
-    for y in 0..height {
+    for (y, row) in grid.rows().enumerate() {
         let mut curr_num = None;
         let mut adj_symbol = false;
 
-        for x in 0..width {
-            match grid[y][x] {
+        for (x, cell) in row.iter().enumerate() {
+            match cell {
                 Cell::Digit(d) => {
                     adj_symbol = adj_symbol || has_adjacent_symbol(&grid, x, y);
                     curr_num = match curr_num {
-                        Some(n) => Some(n * 10 + d as u32),
-                        None => Some(d as u32),
+                        Some(n) => Some(n * 10 + *d as u32),
+                        None => Some(*d as u32),
                     };
                 }
                 _ => {
@@ -120,84 +93,72 @@ pub fn solve_part1(input: &str) -> u32 {
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-fn get_adjacent_numbers(grid: &[Box<[Cell]>], x: usize, y: usize) -> Vec<u32> {
-    let width = grid[0].len();
-    let height = grid.len();
-
+fn get_adjacent_numbers(grid: &Grid<Cell>, x: usize, y: usize) -> Vec<u32> {
     let mut adj_nums = Vec::new();
     let mut seen = HashSet::new();
 
-    for y_offset in -1..=1 {
-        for x_offset in -1..=1 {
-            if x_offset == 0 && y_offset == 0 {
-                continue;
-            }
+    for (x, y) in grid.neighbors8((x, y)) {
+        let row = grid.row(y);
 
-            let x = x as i32 + x_offset;
-            let y = y as i32 + y_offset;
+        if let Cell::Digit(_) = row[x] {
+            let mut x = x;
 
-            if x < 0 || x >= width as i32 || y < 0 || y >= height as i32 {
-                continue;
+            while x > 0 && matches!(row[x - 1], Cell::Digit(_)) {
+                x -= 1;
             }
 
-            if let Cell::Digit(_) = grid[y as usize][x as usize] {
-                let mut x = x;
-
-                while x > 0 && matches!(grid[y as usize][(x - 1) as usize], Cell::Digit(_)) {
-                    x -= 1;
-                }
-
-                if !seen.insert((x, y)) {
-                    continue;
-                }
+            if !seen.insert((x, y)) {
+                continue;
+            }
 
-                let mut num = if let Cell::Digit(d) = grid[y as usize][x as usize] {
-                    d as u32
-                } else {
-                    unreachable!()
-                };
-
-                while x + 1 < width as i32
-                    && matches!(grid[y as usize][(x + 1) as usize], Cell::Digit(_))
-                {
-                    x += 1;
-
-                    if let Cell::Digit(d) = grid[y as usize][x as usize] {
-                        num = num * 10 + d as u32;
-                    } else {
-                        unreachable!()
-                    }
-                }
+            let num = row[x..]
+                .iter()
+                .map_while(|cell| match cell {
+                    Cell::Digit(d) => Some(*d as u32),
+                    _ => None,
+                })
+                .fold(0, |num, d| num * 10 + d);
 
-                adj_nums.push(num);
-            }
+            adj_nums.push(num);
         }
     }
 
     adj_nums
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let grid = parse_grid(input);
-    let width = grid[0].len();
-    let height = grid.len();
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_grid(input)?;
 
     let mut sum = 0;
 
-    for y in 0..height {
-        for x in 0..width {
-            if let Cell::Symbol('*') = grid[y][x] {
-                let adj_nums = get_adjacent_numbers(&grid, x, y);
+    for ((x, y), cell) in grid.iter() {
+        if let Cell::Symbol('*') = cell {
+            let adj_nums = get_adjacent_numbers(&grid, x, y);
 
-                if adj_nums.len() == 2 {
-                    sum += adj_nums[0] * adj_nums[1];
-                }
+            if adj_nums.len() == 2 {
+                sum += adj_nums[0] * adj_nums[1];
             }
         }
     }
 
-    sum
+    Ok(sum)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_grid(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
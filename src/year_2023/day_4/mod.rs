@@ -2,11 +2,17 @@ use std::collections::HashSet;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{multispace1, newline, u8},
+    character::complete::{multispace1, u8},
     multi::separated_list1,
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,7 +25,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 13);
+            assert_eq!(result, Ok(13));
         }
     }
 
@@ -29,7 +35,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 30);
+            assert_eq!(result, Ok(30));
         }
     }
 }
@@ -73,30 +79,45 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
 }
 
 fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
-    separated_list1(newline, parse_card)(input)
+    lines(parse_card)(input)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (_, cards) = parse_cards(input).unwrap();
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    let cards = parse_all(input, parse_cards)?;
 
-    cards
+    Ok(cards
         .iter()
         .map(|c| c.matching_cards())
         .filter(|&c| c > 0)
         .map(|c| 2u32.pow((c - 1) as u32))
-        .sum()
+        .sum())
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (_, cards) = parse_cards(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    let cards = parse_all(input, parse_cards)?;
 
     let mut scratchcards = vec![0; cards.len()];
 
     for (i, matches) in cards.iter().map(|c| c.matching_cards()).enumerate().rev() {
-        scratchcards[i] = 1 + scratchcards[i + 1..i + 1 + matches as usize]
-            .iter()
-            .sum::<u32>();
+        let last = (i + 1 + matches as usize).min(cards.len());
+        scratchcards[i] = 1 + scratchcards[i + 1..last].iter().sum::<u32>();
     }
 
-    scratchcards.iter().sum()
+    Ok(scratchcards.iter().sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse_cards).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
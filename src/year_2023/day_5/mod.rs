@@ -2,13 +2,20 @@ use std::ops::Range;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{newline, u64},
-    error::Error,
+    character::complete::{line_ending, u64},
+    combinator::map,
     multi::separated_list1,
-    sequence::{preceded, separated_pair},
+    sequence::{pair, preceded, separated_pair, tuple},
     IResult,
 };
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::{blank_line, lines},
+    ranges::{RangeMap, RangeSet},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,7 +28,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 35);
+            assert_eq!(result, Ok(35));
         }
     }
 
@@ -31,55 +38,36 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 46);
+            assert_eq!(result, Ok(46));
         }
     }
 }
 
-type RangeMap = (Range<u64>, Range<u64>);
-
-fn parse_range_map(input: &str) -> IResult<&str, RangeMap> {
+fn parse_range_map(input: &str) -> IResult<&str, (Range<u64>, u64)> {
     let (input, dest_start) = u64(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, src_start) = u64(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, len) = u64(input)?;
 
-    Ok((
-        input,
-        (src_start..(src_start + len), dest_start..(dest_start + len)),
-    ))
+    Ok((input, (src_start..(src_start + len), dest_start)))
 }
 
-fn parse_range_maps(input: &str) -> IResult<&str, Vec<Vec<RangeMap>>> {
-    let (input, seed_to_soil) = preceded(
-        tag("\n\nseed-to-soil map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, soil_to_fertilizer) = preceded(
-        tag("\n\nsoil-to-fertilizer map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, fertilizer_to_water) = preceded(
-        tag("\n\nfertilizer-to-water map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, water_to_light) = preceded(
-        tag("\n\nwater-to-light map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, light_to_temperature) = preceded(
-        tag("\n\nlight-to-temperature map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, temperature_to_humidity) = preceded(
-        tag("\n\ntemperature-to-humidity map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
-    let (input, humidity_to_location) = preceded(
-        tag("\n\nhumidity-to-location map:\n"),
-        separated_list1(newline, parse_range_map),
-    )(input)?;
+fn parse_section<'a>(name: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, RangeMap<u64>> {
+    preceded(
+        tuple((blank_line, tag(name), tag(" map:"), line_ending)),
+        map(lines(parse_range_map), RangeMap::from_iter),
+    )
+}
+
+fn parse_range_maps(input: &str) -> IResult<&str, Vec<RangeMap<u64>>> {
+    let (input, seed_to_soil) = parse_section("seed-to-soil")(input)?;
+    let (input, soil_to_fertilizer) = parse_section("soil-to-fertilizer")(input)?;
+    let (input, fertilizer_to_water) = parse_section("fertilizer-to-water")(input)?;
+    let (input, water_to_light) = parse_section("water-to-light")(input)?;
+    let (input, light_to_temperature) = parse_section("light-to-temperature")(input)?;
+    let (input, temperature_to_humidity) = parse_section("temperature-to-humidity")(input)?;
+    let (input, humidity_to_location) = parse_section("humidity-to-location")(input)?;
 
     Ok((
         input,
@@ -100,78 +88,51 @@ fn parse_range(input: &str) -> IResult<&str, Range<u64>> {
     Ok((input, start..(start + len)))
 }
 
-fn map_range(range: &Range<u64>, range_maps: &[RangeMap]) -> Vec<Range<u64>> {
-    let mut ranges = vec![];
-    let mut start = range.start;
-
-    while start < range.end {
-        if let Some((src_range, dest_range)) = range_maps
-            .iter()
-            .find(|(src_range, _)| src_range.contains(&start))
-        {
-            let end = std::cmp::min(range.end, src_range.end);
-            ranges.push(
-                dest_range.start + (start - src_range.start)
-                    ..dest_range.start + (end - src_range.start),
-            );
-            start = end;
-        } else if let Some((src_range, _)) = range_maps
-            .iter()
-            .find(|(src_range, _)| (start..range.end).contains(&src_range.start))
-        {
-            let end = src_range.start;
-            ranges.push(start..end);
-            start = end;
-        } else {
-            ranges.push(start..range.end);
-            start = range.end;
-        }
-    }
-
-    ranges
+fn solve(seeds: RangeSet<u64>, range_maps: &[RangeMap<u64>]) -> Result<u64, SolveError> {
+    range_maps
+        .iter()
+        .fold(seeds, |seeds, range_map| range_map.apply(&seeds))
+        .min()
+        .ok_or_else(|| SolveError::no_solution("no seeds to plant"))
 }
 
-fn solve(ranges: &[Range<u64>], range_maps: &Vec<Vec<RangeMap>>) -> u64 {
-    let mut ranges = ranges.to_vec();
-
-    for range_map in range_maps {
-        ranges = ranges
-            .iter()
-            .flat_map(|range| map_range(range, range_map))
-            .collect();
-    }
-
-    ranges
-        .into_iter()
-        .map(|Range { start, .. }| start)
-        .min()
-        .unwrap()
+fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
+    preceded(tag("seeds: "), separated_list1(tag(" "), u64))(input)
 }
 
-pub fn solve_part1(input: &str) -> u64 {
-    let (input, seeds) = preceded(
-        tag::<&str, &str, Error<&str>>("seeds: "),
-        separated_list1(tag(" "), u64),
-    )(input)
-    .unwrap();
-    let (_, range_maps) = parse_range_maps(input).unwrap();
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    let (seeds, range_maps) = parse_all(input, pair(parse_seeds, parse_range_maps))?;
 
     solve(
-        &seeds
-            .iter()
-            .map(|&value| value..(value + 1))
-            .collect::<Vec<_>>(),
+        seeds.iter().map(|&value| value..(value + 1)).collect(),
         &range_maps,
     )
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    let (input, ranges) = preceded(
-        tag::<&str, &str, Error<&str>>("seeds: "),
-        separated_list1(tag(" "), parse_range),
-    )(input)
-    .unwrap();
-    let (_, range_maps) = parse_range_maps(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let (ranges, range_maps) = parse_all(
+        input,
+        pair(
+            preceded(tag("seeds: "), separated_list1(tag(" "), parse_range)),
+            parse_range_maps,
+        ),
+    )?;
+
+    solve(ranges.into_iter().collect(), &range_maps)
+}
 
-    solve(&ranges, &range_maps)
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, pair(parse_seeds, parse_range_maps)).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
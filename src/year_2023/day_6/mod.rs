@@ -1,5 +1,10 @@
 use itertools::Itertools;
 
+use crate::{
+    error::{parse_number, SolveError},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,7 +17,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 288);
+            assert_eq!(result, Ok(288));
         }
     }
 
@@ -22,7 +27,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 71503);
+            assert_eq!(result, Ok(71503));
         }
     }
 }
@@ -33,23 +38,51 @@ struct Round {
     record: u64,
 }
 
-fn parse_input_1(input: &str) -> Vec<Round> {
-    let (time, record) = input.lines().next_tuple().unwrap();
+fn parse_lines(input: &str) -> Result<(&str, &str), SolveError> {
+    let (time, record) = input
+        .lines()
+        .next_tuple()
+        .ok_or_else(|| SolveError::parse(input, input, "expected time and distance lines"))?;
+
+    if time.split_whitespace().count() != record.split_whitespace().count() {
+        return Err(SolveError::parse(
+            input,
+            record,
+            "expected a distance for every time",
+        ));
+    }
+
+    Ok((time, record))
+}
+
+fn parse_input_1(input: &str) -> Result<Vec<Round>, SolveError> {
+    let (time, record) = parse_lines(input)?;
+
     time.split_whitespace()
         .zip(record.split_whitespace())
         .skip(1)
-        .map(|(time, record)| Round {
-            time: time.parse().unwrap(),
-            record: record.parse().unwrap(),
+        .map(|(time, record)| {
+            Ok(Round {
+                time: parse_number(input, time)?,
+                record: parse_number(input, record)?,
+            })
         })
         .collect()
 }
 
-fn parse_input_2(input: &str) -> Round {
-    let (time, record) = input.lines().next_tuple().unwrap();
-    let time = time.split_whitespace().skip(1).join("").parse().unwrap();
-    let record = record.split_whitespace().skip(1).join("").parse().unwrap();
-    Round { time, record }
+fn parse_input_2(input: &str) -> Result<Round, SolveError> {
+    let (time_line, record_line) = parse_lines(input)?;
+
+    let time = time_line.split_whitespace().skip(1).join("");
+    let time = time
+        .parse()
+        .map_err(|_| SolveError::parse(input, time_line, "expected a time"))?;
+    let record = record_line.split_whitespace().skip(1).join("");
+    let record = record
+        .parse()
+        .map_err(|_| SolveError::parse(input, record_line, "expected a distance"))?;
+
+    Ok(Round { time, record })
 }
 
 fn count_wins(Round { time, record }: &Round) -> u64 {
@@ -73,10 +106,26 @@ fn count_wins(Round { time, record }: &Round) -> u64 {
     max_hold_time - min_hold_time
 }
 
-pub fn solve_part1(input: &str) -> u64 {
-    parse_input_1(input).iter().map(count_wins).product()
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    Ok(parse_input_1(input)?.iter().map(count_wins).product())
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    count_wins(&parse_input_2(input))
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    Ok(count_wins(&parse_input_2(input)?))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_input_1(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
@@ -1,15 +1,16 @@
 use itertools::Itertools;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{newline, u32},
-    combinator::map,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    branch::alt, bytes::complete::tag, character::complete::u32, combinator::map,
+    sequence::separated_pair, IResult,
 };
 use std::cmp::Ordering;
 
+use crate::{
+    error::{parse_all, SolveError},
+    parsers::lines,
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,7 +23,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 6440);
+            assert_eq!(result, Ok(6440));
         }
     }
 
@@ -32,7 +33,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 5905);
+            assert_eq!(result, Ok(5905));
         }
     }
 }
@@ -253,28 +254,40 @@ fn parse_line(input: &str) -> IResult<&str, (Hand, u32)> {
     separated_pair(parse_hand, tag(" "), u32)(input)
 }
 fn parse_input(input: &str) -> IResult<&str, Vec<(Hand, u32)>> {
-    separated_list1(newline, parse_line)(input)
+    lines(parse_line)(input)
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    parse_input(input)
-        .unwrap()
-        .1
+pub fn solve_part1(input: &str) -> Result<u32, SolveError> {
+    Ok(parse_all(input, parse_input)?
         .iter()
         .sorted()
         .enumerate()
         .map(|(i, (_, score))| score * (i as u32 + 1))
-        .sum()
+        .sum())
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    parse_input(input)
-        .unwrap()
-        .1
+pub fn solve_part2(input: &str) -> Result<u32, SolveError> {
+    Ok(parse_all(input, parse_input)?
         .iter()
         .map(|(hand, score)| (HandWithJokers::from(hand), *score))
         .sorted()
         .enumerate()
         .map(|(i, (_, score))| score * (i as u32 + 1))
-        .sum()
+        .sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_all(input, parse_input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
 }
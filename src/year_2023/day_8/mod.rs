@@ -1,15 +1,22 @@
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    character::complete::newline,
     combinator::map,
-    multi::{count, many1, separated_list1},
+    multi::many1,
     sequence::{delimited, separated_pair},
     IResult,
 };
 use num::integer::lcm;
 use std::collections::HashMap;
 
+use crate::{
+    dot::Dot,
+    error::{parse_all, SolveError},
+    parsers::{blank_line, lines},
+    solution::{Answer, Solution},
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -18,19 +25,40 @@ mod tests {
     const EXAMPLE_2: &str = include_str!("example_2.in");
     const EXAMPLE_3: &str = include_str!("example_3.in");
 
+    #[test]
+    fn it_exports_dot() {
+        let (_, graph) = parse(EXAMPLE_2).unwrap();
+
+        assert_eq!(
+            dot(&graph).to_string(),
+            "digraph network {
+    \"AAA\" [shape=\"invhouse\"];
+    \"BBB\";
+    \"ZZZ\" [shape=\"doublecircle\"];
+    \"AAA\" -> \"BBB\" [label=\"L\"];
+    \"AAA\" -> \"BBB\" [label=\"R\"];
+    \"BBB\" -> \"AAA\" [label=\"L\"];
+    \"BBB\" -> \"ZZZ\" [label=\"R\"];
+    \"ZZZ\" -> \"ZZZ\" [label=\"L\"];
+    \"ZZZ\" -> \"ZZZ\" [label=\"R\"];
+}
+"
+        );
+    }
+
     mod part1 {
         use super::*;
 
         #[test]
         fn it_solves_example_1() {
             let result = solve_part1(EXAMPLE_1);
-            assert_eq!(result, 2);
+            assert_eq!(result, Ok(2));
         }
 
         #[test]
         fn it_solves_example_2() {
             let result = solve_part1(EXAMPLE_2);
-            assert_eq!(result, 6);
+            assert_eq!(result, Ok(6));
         }
     }
 
@@ -40,7 +68,7 @@ mod tests {
         #[test]
         fn it_solves_example_3() {
             let result = solve_part2(EXAMPLE_3);
-            assert_eq!(result, 6);
+            assert_eq!(result, Ok(6));
         }
     }
 }
@@ -73,13 +101,52 @@ fn parse_edge(input: &str) -> IResult<&str, (&str, (&str, &str))> {
         ),
     )(input)
 }
-fn parse_graph(input: &str) -> IResult<&str, Graph> {
-    let (input, edges) = separated_list1(newline, parse_edge)(input)?;
+fn parse_graph(input: &str) -> IResult<&str, Graph<'_>> {
+    let (input, edges) = lines(parse_edge)(input)?;
 
     Ok((input, Graph::from_iter(edges)))
 }
-fn parse_input(input: &str) -> IResult<&str, (Vec<Instruction>, Graph)> {
-    separated_pair(parse_instructions, count(newline, 2), parse_graph)(input)
+fn parse_input(input: &str) -> IResult<&str, (Vec<Instruction>, Graph<'_>)> {
+    separated_pair(parse_instructions, blank_line, parse_graph)(input)
+}
+
+fn parse(input: &str) -> Result<(Vec<Instruction>, Graph<'_>), SolveError> {
+    let (instructions, graph) = parse_all(input, parse_input)?;
+
+    for (left, right) in graph.values() {
+        for vertex in [left, right] {
+            if !graph.contains_key(vertex) {
+                return Err(SolveError::parse(
+                    input,
+                    vertex,
+                    format!("unknown node {vertex}"),
+                ));
+            }
+        }
+    }
+
+    Ok((instructions, graph))
+}
+
+// Nodes ending in A, where the ghosts start, are house-shaped and the ones ending in Z circled twice
+fn dot(graph: &Graph) -> Dot {
+    let mut dot = Dot::digraph("network");
+
+    for node in graph.keys().sorted() {
+        if node.ends_with('A') {
+            dot.node(node, &[("shape", "invhouse")]);
+        } else if node.ends_with('Z') {
+            dot.node(node, &[("shape", "doublecircle")]);
+        } else {
+            dot.node(node, &[]);
+        }
+    }
+    for (node, (left, right)) in graph.iter().sorted() {
+        dot.edge(node, left, &[("label", "L")])
+            .edge(node, right, &[("label", "R")]);
+    }
+
+    dot
 }
 
 fn solve(
@@ -104,18 +171,43 @@ fn solve(
         + 1
 }
 
-pub fn solve_part1(input: &str) -> u64 {
-    let (_, (instructions, graph)) = parse_input(input).unwrap();
-    solve(&graph, &instructions, "AAA", |k| k == "ZZZ")
+pub fn solve_part1(input: &str) -> Result<u64, SolveError> {
+    let (instructions, graph) = parse(input)?;
+
+    if !graph.contains_key("AAA") {
+        return Err(SolveError::no_solution("no AAA node"));
+    }
+
+    Ok(solve(&graph, &instructions, "AAA", |k| k == "ZZZ"))
 }
 
-pub fn solve_part2(input: &str) -> u64 {
-    let (_, (instructions, graph)) = parse_input(input).unwrap();
+pub fn solve_part2(input: &str) -> Result<u64, SolveError> {
+    let (instructions, graph) = parse(input)?;
 
     graph
         .keys()
-        .filter(|k| k.chars().nth(2).unwrap() == 'A')
+        .filter(|k| k.ends_with('A'))
         .map(|k| solve(&graph, &instructions, k, |k| k.ends_with('Z')))
         .reduce(lcm)
-        .unwrap()
+        .ok_or_else(|| SolveError::no_solution("no starting nodes"))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+
+    fn dot(&self, input: &str) -> Option<Result<Dot, SolveError>> {
+        Some(parse(input).map(|(_, graph)| dot(&graph)))
+    }
 }
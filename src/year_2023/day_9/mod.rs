@@ -1,4 +1,7 @@
-use itertools::Itertools;
+use crate::{
+    error::{parse_number, SolveError},
+    solution::{Answer, Solution},
+};
 
 #[cfg(test)]
 mod tests {
@@ -12,7 +15,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part1(EXAMPLE);
-            assert_eq!(result, 114);
+            assert_eq!(result, Ok(114));
         }
     }
 
@@ -22,7 +25,7 @@ mod tests {
         #[test]
         fn it_solves_example() {
             let result = solve_part2(EXAMPLE);
-            assert_eq!(result, 2);
+            assert_eq!(result, Ok(2));
         }
     }
 }
@@ -48,21 +51,43 @@ fn predict(dir: &PredictionDirection, arr: &[i32]) -> i32 {
     }
 }
 
-fn solve(input: &str, dir: &PredictionDirection) -> i32 {
+fn parse_histories(input: &str) -> Result<Vec<Vec<i32>>, SolveError> {
     input
         .lines()
         .map(|line| {
             line.split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect_vec()
+                .map(|s| parse_number(input, s))
+                .collect()
         })
-        .map(|arr| predict(dir, &arr))
-        .sum()
+        .collect()
 }
 
-pub fn solve_part1(input: &str) -> i32 {
+fn solve(input: &str, dir: &PredictionDirection) -> Result<i32, SolveError> {
+    Ok(parse_histories(input)?
+        .iter()
+        .map(|arr| predict(dir, arr))
+        .sum())
+}
+
+pub fn solve_part1(input: &str) -> Result<i32, SolveError> {
     solve(input, &PredictionDirection::Next)
 }
-pub fn solve_part2(input: &str) -> i32 {
+pub fn solve_part2(input: &str) -> Result<i32, SolveError> {
     solve(input, &PredictionDirection::Prev)
 }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_histories(input).map(|_| ()))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Option<Result<Answer, SolveError>> {
+        Some(solve_part2(input).map(Answer::from))
+    }
+}
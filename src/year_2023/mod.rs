@@ -21,3 +21,31 @@ pub mod day_6;
 pub mod day_7;
 pub mod day_8;
 pub mod day_9;
+
+use crate::solution::Registry;
+
+pub const DAYS: Registry = &[
+    (1, &day_1::Solver),
+    (2, &day_2::Solver),
+    (3, &day_3::Solver),
+    (4, &day_4::Solver),
+    (5, &day_5::Solver),
+    (6, &day_6::Solver),
+    (7, &day_7::Solver),
+    (8, &day_8::Solver),
+    (9, &day_9::Solver),
+    (10, &day_10::Solver),
+    (11, &day_11::Solver),
+    (12, &day_12::Solver),
+    (13, &day_13::Solver),
+    (14, &day_14::Solver),
+    (15, &day_15::Solver),
+    (16, &day_16::Solver),
+    (17, &day_17::Solver),
+    (18, &day_18::Solver),
+    (19, &day_19::Solver),
+    (20, &day_20::Solver),
+    (21, &day_21::Solver),
+    (22, &day_22::Solver),
+    (23, &day_23::Solver),
+];